@@ -1,7 +1,11 @@
 use crate::{
     error::ContractError,
     executions::{self, settle_auction},
-    helpers::{new_auction_round, validate_percentage},
+    helpers::{
+        new_auction_round, validate_bid_strategy, validate_exit_fee_schedule,
+        validate_keeper_unbonding_period, validate_percentage,
+    },
+    pricing::validate_pricing,
     queries,
     state::{Whitelisted, CONFIG, FUNDS_LOCKED, WHITELISTED_ADDRESSES},
 };
@@ -45,19 +49,31 @@ pub fn instantiate(
         whitelisted.push(attr("whitelisted_address", addr.to_string()));
     }
 
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            native_denom: msg.native_denom,
-            min_balance: msg.min_balance,
-            token_factory_type: msg.token_factory_type.clone(),
-            rewards_fee: validate_percentage(msg.rewards_fee)?,
-            rewards_fee_addr: deps.api.addr_validate(&msg.rewards_fee_addr)?,
-            min_next_bid_increment_rate: validate_percentage(msg.min_next_bid_increment_rate)?,
-            treasury_chest_code_id: msg.treasury_chest_code_id,
-            min_return: validate_percentage(msg.min_return)?,
-        },
-    )?;
+    let config = Config {
+        native_denom: msg.native_denom,
+        min_balance: msg.min_balance,
+        token_factory_type: msg.token_factory_type.clone(),
+        rewards_fee: validate_percentage(msg.rewards_fee)?,
+        rewards_fee_addr: deps.api.addr_validate(&msg.rewards_fee_addr)?,
+        min_next_bid_increment_rate: validate_percentage(msg.min_next_bid_increment_rate)?,
+        treasury_chest_code_id: msg.treasury_chest_code_id,
+        min_return: validate_percentage(msg.min_return)?,
+        pricing: validate_pricing(deps.api, msg.pricing.unwrap_or_default())?,
+        bid_strategy: validate_bid_strategy(msg.bid_strategy.unwrap_or_default())?,
+        bid_window: msg.bid_window.unwrap_or_default(),
+        lock_period_seconds: msg.lock_period_seconds.unwrap_or_default(),
+        rebid_buffer: msg.rebid_buffer.unwrap_or_default(),
+        exit_fee: validate_exit_fee_schedule(msg.exit_fee.unwrap_or_default())?,
+        surplus_policy: msg.surplus_policy.unwrap_or_default(),
+        config_timelock: msg.config_timelock.unwrap_or_default(),
+        keeper_min_bond: msg.keeper_min_bond.unwrap_or_default(),
+        keeper_unbonding_period: msg.keeper_unbonding_period.unwrap_or_default(),
+        settlement_challenge_period: msg.settlement_challenge_period.unwrap_or_default(),
+        settlement_quorum: msg.settlement_quorum.unwrap_or(1),
+        min_deposit: msg.min_deposit.unwrap_or_default(),
+    };
+    validate_keeper_unbonding_period(deps.as_ref(), &config)?;
+    CONFIG.save(deps.storage, &config)?;
 
     FUNDS_LOCKED.save(deps.storage, &false)?;
 
@@ -78,19 +94,55 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::UpdateConfig {
+            min_next_bid_increment_rate,
+            bid_window,
+            lock_period_seconds,
+            rebid_buffer,
+            keeper_min_bond,
+            keeper_unbonding_period,
+            settlement_challenge_period,
+            settlement_quorum,
+            min_deposit,
+        } => executions::update_config(
+            deps,
+            env,
+            info,
+            min_next_bid_increment_rate,
+            bid_window,
+            lock_period_seconds,
+            rebid_buffer,
+            keeper_min_bond,
+            keeper_unbonding_period,
+            settlement_challenge_period,
+            settlement_quorum,
+            min_deposit,
+        ),
+        ExecuteMsg::ProposeConfig {
             rewards_fee,
             rewards_fee_addr,
-            min_next_bid_increment_rate,
             min_return,
-        } => executions::update_config(
+            pricing,
+            bid_strategy,
+            exit_fee,
+            surplus_policy,
+            treasury_chest_code_id,
+            config_timelock,
+        } => executions::propose_config(
             deps,
             env,
             info,
             rewards_fee,
             rewards_fee_addr,
-            min_next_bid_increment_rate,
             min_return,
+            pricing,
+            bid_strategy,
+            exit_fee,
+            surplus_policy,
+            treasury_chest_code_id,
+            config_timelock,
         ),
+        ExecuteMsg::ApplyConfig {} => executions::apply_config(deps, env, info),
+        ExecuteMsg::CancelConfigProposal {} => executions::cancel_config_proposal(deps, env, info),
         ExecuteMsg::UpdateOwnership(action) => {
             cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
             Ok(Response::default())
@@ -99,27 +151,108 @@ pub fn execute(
             remove,
             add,
         } => executions::update_whitelisted_addresses(deps, env, info, remove, add),
+        ExecuteMsg::GrantRole {
+            role,
+            address,
+            expires_at,
+        } => executions::grant_role(deps, env, info, role, address, expires_at),
+        ExecuteMsg::RevokeRole {
+            role,
+            address,
+        } => executions::revoke_role(deps, env, info, role, address),
+        ExecuteMsg::UpdatePauseStatus {
+            join,
+            exit,
+            bid,
+            settle,
+        } => executions::update_pause_status(deps, env, info, join, exit, bid, settle),
         ExecuteMsg::TryBid {
             auction_round,
             basket_value,
-        } => executions::try_bid(deps, env, info, auction_round, basket_value),
+            max_bid,
+            expected_highest_bid,
+            slippage,
+        } => executions::try_bid(
+            deps,
+            env,
+            info,
+            auction_round,
+            basket_value,
+            max_bid,
+            expected_highest_bid,
+            slippage,
+        ),
         ExecuteMsg::JoinPool {
             auction_round,
-        } => executions::join_pool(deps, env, info, auction_round),
+            rounds,
+        } => executions::join_pool(deps, env, info, auction_round, rounds),
+        ExecuteMsg::CancelDeposit {
+            auction_round,
+        } => executions::cancel_deposit(deps, env, info, auction_round),
         ExecuteMsg::ExitPool {} => executions::exit_pool(deps, env, info),
+        ExecuteMsg::RequestExit {} => executions::request_exit(deps, env, info),
+        ExecuteMsg::CancelExit {} => executions::cancel_exit(deps, env, info),
         ExecuteMsg::SettleAuction {
             auction_round,
             auction_winner,
             auction_winning_bid,
         } => settle_auction(deps, env, info, auction_round, auction_winner, auction_winning_bid),
         ExecuteMsg::TrySettleAuction {} => executions::try_settle_auction(deps, env, info),
+        ExecuteMsg::DisputeSettlement {
+            auction_winner,
+            auction_winning_bid,
+        } => executions::dispute_settlement(deps, env, info, auction_winner, auction_winning_bid),
+        ExecuteMsg::FinalizeSettlement {} => {
+            executions::finalize_pending_settlement(deps, env, info)
+        },
+        ExecuteMsg::ResolveSettlementDispute {
+            auction_winner,
+            auction_winning_bid,
+        } => executions::resolve_settlement_dispute(
+            deps,
+            env,
+            info,
+            auction_winner,
+            auction_winning_bid,
+        ),
+        ExecuteMsg::CatchUpSettlement {} => executions::catch_up_settlement(deps, env, info),
+        ExecuteMsg::AttestAuctionRound {
+            auction_round,
+            auction_winner,
+            auction_winning_bid,
+            basket,
+        } => executions::attest_auction_round(
+            deps,
+            env,
+            info,
+            auction_round,
+            auction_winner,
+            auction_winning_bid,
+            basket,
+        ),
+        ExecuteMsg::BondKeeper {} => executions::bond_keeper(deps, env, info),
+        ExecuteMsg::UnbondKeeper {
+            amount,
+        } => executions::unbond_keeper(deps, env, info, amount),
+        ExecuteMsg::ClaimKeeperBond {} => executions::claim_keeper_bond(deps, env, info),
+        ExecuteMsg::SlashKeeper {
+            auction_round,
+        } => executions::slash_keeper(deps, env, info, auction_round),
+        ExecuteMsg::ProcessSettlementQueue {
+            limit,
+        } => executions::process_settlement_queue_entries(deps, env, info, limit),
+        ExecuteMsg::SweepUntrackedFunds {
+            denom,
+            recipient,
+        } => executions::sweep_untracked_funds(deps, env, info, denom, recipient),
     }
 }
 
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => queries::query_config(deps),
+        QueryMsg::ConfigProposal {} => queries::query_config_proposal(deps),
         QueryMsg::WhitelistedAddresses {} => queries::query_whitelisted_addresses(deps),
         QueryMsg::Ownership {} => {
             let ownership = cw_ownable::get_ownership(deps.storage)?;
@@ -133,6 +266,74 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::FundsLocked {} => to_json_binary(&FUNDS_LOCKED.load(deps.storage)?),
         QueryMsg::CurrentAuctionBasket {} => queries::query_current_auction_basket(deps),
         QueryMsg::UnsettledAuction {} => queries::query_unsettled_auction(deps),
+        QueryMsg::UserPosition {
+            address,
+        } => queries::query_user_position(deps, address),
+        QueryMsg::Depositors {
+            round,
+            start_after,
+            limit,
+        } => queries::query_depositors(deps, round, start_after, limit),
+        QueryMsg::RoundHistory {
+            start_after,
+            limit,
+        } => queries::query_round_history(deps, start_after, limit),
+        QueryMsg::Round {
+            round,
+        } => queries::query_round(deps, round),
+        QueryMsg::Stats {} => queries::query_stats(deps),
+        QueryMsg::BidWindow {} => queries::query_bid_window(deps, env),
+        QueryMsg::LockStatus {} => queries::query_lock_status(deps, env),
+        QueryMsg::PendingDeposits {
+            address,
+        } => queries::query_pending_deposits(deps, address),
+        QueryMsg::PendingDepositTotals {
+            start_after,
+            limit,
+        } => queries::query_pending_deposit_totals(deps, start_after, limit),
+        QueryMsg::Nav {} => queries::query_nav(deps),
+        QueryMsg::WithdrawableNow {
+            address,
+        } => queries::query_withdrawable_now(deps, env, address),
+        QueryMsg::PendingExit {
+            address,
+        } => queries::query_pending_exit(deps, address),
+        QueryMsg::PendingExits {
+            start_after,
+            limit,
+        } => queries::query_pending_exits(deps, start_after, limit),
+        QueryMsg::RoleHolders {
+            role,
+            start_after,
+            limit,
+        } => queries::query_role_holders(deps, env, role, start_after, limit),
+        QueryMsg::KeeperBond {
+            address,
+        } => queries::query_keeper_bond(deps, address),
+        QueryMsg::KeeperBonds {
+            start_after,
+            limit,
+        } => queries::query_keeper_bonds(deps, start_after, limit),
+        QueryMsg::KeeperReports {
+            auction_round,
+            start_after,
+            limit,
+        } => queries::query_keeper_reports(deps, auction_round, start_after, limit),
+        QueryMsg::PendingSettlement {} => queries::query_pending_settlement(deps),
+        QueryMsg::AttestationRequired {} => queries::query_attestation_required(deps),
+        QueryMsg::SettlementQueue {} => queries::query_settlement_queue(deps),
+        QueryMsg::SettlementReport {
+            auction_round,
+        } => queries::query_settlement_report(deps, auction_round),
+        QueryMsg::Slashings {
+            start_after,
+            limit,
+        } => queries::query_slashings(deps, start_after, limit),
+        QueryMsg::PauseStatus {} => queries::query_pause_status(deps),
+        QueryMsg::Commitments {
+            start_after,
+            limit,
+        } => queries::query_commitments(deps, start_after, limit),
     }
 }
 
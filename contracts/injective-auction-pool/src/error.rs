@@ -1,4 +1,7 @@
-use cosmwasm_std::{Decimal, Instantiate2AddressError, OverflowError, StdError, Uint128};
+use cosmwasm_std::{
+    CheckedMultiplyFractionError, Decimal, DivideByZeroError, Instantiate2AddressError,
+    OverflowError, StdError, Uint128,
+};
 use cw_ownable::OwnershipError;
 use cw_utils::PaymentError;
 use thiserror::Error;
@@ -51,6 +54,12 @@ pub enum ContractError {
     #[error("Overflow error: {0}")]
     OverflowError(#[from] OverflowError),
 
+    #[error("Divide by zero error: {0}")]
+    DivideByZeroError(#[from] DivideByZeroError),
+
+    #[error("Multiply fraction error: {0}")]
+    CheckedMultiplyFractionError(#[from] CheckedMultiplyFractionError),
+
     #[error("Instantiate address error: {0}")]
     Instantiate2AddressError(#[from] Instantiate2AddressError),
 
@@ -106,6 +115,207 @@ pub enum ContractError {
         unsettled: u64,
         latest: u64,
     },
+
+    #[error("No price available for {denom}")]
+    PriceUnavailable {
+        denom: String,
+    },
+
+    #[error("Price of {denom} is stale. Last updated at {updated_at}")]
+    StalePrice {
+        denom: String,
+        updated_at: u64,
+    },
+
+    #[error(
+        "Basket value {supplied} differs from the on-chain valuation {on_chain} by more than the \
+         allowed tolerance"
+    )]
+    BasketValueMismatch {
+        supplied: Uint128,
+        on_chain: Uint128,
+    },
+
+    #[error("A basket value must be provided as no price sources are configured")]
+    BasketValueRequired,
+
+    #[error(
+        "The spot market price of {denom} can be manipulated, a basket value must be provided"
+    )]
+    SpotPriceRequiresBasketValue {
+        denom: String,
+    },
+
+    #[error("Price feed {feed_id} cannot be used: {reason}")]
+    InvalidPriceFeed {
+        feed_id: String,
+        reason: String,
+    },
+
+    #[error("Invalid bid strategy: {reason}")]
+    InvalidBidStrategy {
+        reason: String,
+    },
+
+    #[error("No pending exit found for {address}")]
+    NoPendingExit {
+        address: String,
+    },
+
+    #[error("No pending deposit found for {address} on auction round {auction_round}")]
+    NoPendingDeposit {
+        address: String,
+        auction_round: u64,
+    },
+
+    #[error("Invalid exit fee schedule: {reason}")]
+    InvalidExitFeeSchedule {
+        reason: String,
+    },
+
+    #[error("Keepers must bond at least {min_bond} to settle the auction")]
+    InsufficientKeeperBond {
+        min_bond: Uint128,
+    },
+
+    #[error("No keeper bond found for {address}")]
+    NoKeeperBond {
+        address: String,
+    },
+
+    #[error("The keeper bond unbonding cannot be claimed before {release_at}")]
+    KeeperBondNotReleased {
+        release_at: u64,
+    },
+
+    #[error("The keeper bond is frozen until auction round {auction_round} is settled")]
+    KeeperBondFrozen {
+        auction_round: u64,
+    },
+
+    #[error("The keeper unbonding period must be at least {min_unbonding_period} seconds")]
+    KeeperUnbondingPeriodTooShort {
+        min_unbonding_period: u64,
+    },
+
+    #[error("A settlement of auction round {auction_round} is already pending")]
+    SettlementPending {
+        auction_round: u64,
+    },
+
+    #[error("No pending settlement found")]
+    NoPendingSettlement {},
+
+    #[error("The pending settlement of auction round {auction_round} is disputed")]
+    SettlementDisputed {
+        auction_round: u64,
+    },
+
+    #[error("The pending settlement of auction round {auction_round} is not disputed")]
+    SettlementNotDisputed {
+        auction_round: u64,
+    },
+
+    #[error("The pending settlement cannot be finalized before {finalizes_at}")]
+    SettlementChallengePeriodActive {
+        finalizes_at: u64,
+    },
+
+    #[error("The pending settlement cannot be disputed after {finalizes_at}")]
+    DisputeWindowClosed {
+        finalizes_at: u64,
+    },
+
+    #[error("The dispute matches the pending settlement")]
+    DisputeMatchesSettlement {},
+
+    #[error("No auction round was missed since auction round {unsettled}")]
+    NoMissedAuctionRounds {
+        unsettled: u64,
+    },
+
+    #[error("Auction round {auction_round} needs an attestation from the owner")]
+    AttestationRequired {
+        auction_round: u64,
+    },
+
+    #[error("Auction round {auction_round} doesn't need an attestation")]
+    NoAttestationRequired {
+        auction_round: u64,
+    },
+
+    #[error("No settlement report found for auction round {auction_round}")]
+    NoSettlementReport {
+        auction_round: u64,
+    },
+
+    #[error("The keeper that settled auction round {auction_round} was already slashed")]
+    ReportAlreadySlashed {
+        auction_round: u64,
+    },
+
+    #[error("The settlement report of auction round {auction_round} matches the auction result")]
+    ReportMatchesAuctionResult {
+        auction_round: u64,
+    },
+
+    #[error("Role {role} is not granted to {address}")]
+    RoleNotGranted {
+        role: String,
+        address: String,
+    },
+
+    #[error("No config change has been proposed")]
+    NoConfigProposal {},
+
+    #[error("The proposed config change cannot be applied before {activates_at}")]
+    ConfigProposalNotActive {
+        activates_at: u64,
+    },
+
+    #[error("The {operation} operation is paused")]
+    Paused {
+        operation: String,
+    },
+
+    #[error("Invalid commitment: {reason}")]
+    InvalidCommitment {
+        reason: String,
+    },
+
+    #[error("Duplicated price source for {denom}")]
+    DuplicatePriceSource {
+        denom: String,
+    },
+
+    #[error("The price source of {denom} requires a maximum price age")]
+    MissingMaxPriceAge {
+        denom: String,
+    },
+
+    #[error("The amount {amount} is below the minimum of {min_deposit}")]
+    BelowMinDeposit {
+        amount: Uint128,
+        min_deposit: Uint128,
+    },
+
+    #[error("The settlement of auction round {auction_round} is still being processed")]
+    SettlementQueuePending {
+        auction_round: u64,
+    },
+
+    #[error("No settlement is left to process")]
+    NoSettlementQueue,
+
+    #[error("Funds in {denom} are owed by the contract and cannot be swept")]
+    TrackedDenom {
+        denom: String,
+    },
+
+    #[error("No funds in {denom} to sweep")]
+    NoUntrackedFunds {
+        denom: String,
+    },
 }
 
 impl From<semver::Error> for ContractError {
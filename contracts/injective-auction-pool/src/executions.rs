@@ -1,49 +1,94 @@
 use cosmwasm_std::{
-    attr, coins, BankMsg, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response, Uint128,
+    attr, coins, to_json_binary, Addr, Attribute, BankMsg, CosmosMsg, Decimal, Deps, DepsMut,
+    Empty, Env, MessageInfo, OverflowError, Response, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw_storage_plus::{Bound, PrefixBound};
+use injective_auction::auction_pool::{
+    BidStrategy, Commitment, ConfigProposal, ExitFeeSchedule, KeeperBond, KeeperReport,
+    PendingExit, PendingSettlement, PricingConfig, Role, RoundBid, RoundRecord, SettlementDispute,
+    SettlementReport, Slashing, SurplusPolicy,
 };
 use injective_std::types::cosmos::base::v1beta1::Coin;
 use injective_std::types::injective::auction::v1beta1::MsgBid;
 
 use crate::{
     helpers::{
-        new_auction_round, query_current_auction, query_latest_auction_result, validate_percentage,
+        add_pending_deposit, add_to_pool, assert_not_paused, assert_role,
+        assert_settlement_queue_empty, compute_bid_amount, exit_fee_rate, has_role, lock_status,
+        new_auction_round, pool_nav, process_settlement_queue, query_auction_period,
+        query_current_auction, query_latest_auction_result, record_deposit, release_deposits,
+        remove_from_pool, remove_pending_deposit, shares_value, validate_bid_strategy,
+        validate_exit_fee_schedule, validate_keeper_unbonding_period, validate_percentage,
+        withdrawable_surplus, SETTLEMENT_BATCH_SIZE,
     },
+    pricing::{check_basket_value, query_basket_value, spot_priced_denom, validate_pricing},
     state::{
-        Whitelisted, BIDDING_BALANCE, CONFIG, FUNDS_LOCKED, UNSETTLED_AUCTION,
+        Auction, RoleGrant, Whitelisted, ATTESTATION_REQUIRED, BIDDING_BALANCE, COMMITMENTS,
+        COMMITMENT_EXPIRIES, CONFIG, CONFIG_PROPOSAL, FUNDS_LOCKED, KEEPER_BONDS, KEEPER_REPORTS,
+        PAUSE_STATUS, PENDING_EXITS, PENDING_SETTLEMENT, ROLES, ROUND_BIDS, ROUND_HISTORY,
+        SETTLEMENT_QUEUE, SETTLEMENT_REPORTS, SLASHINGS, STATS, TOTAL_COMMITTED,
+        TOTAL_KEEPER_BONDS, TOTAL_PENDING_EXITS, TOTAL_PENDING_EXIT_FEES, UNSETTLED_AUCTION,
         WHITELISTED_ADDRESSES,
     },
     ContractError,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    rewards_fee: Option<Decimal>,
-    rewards_fee_addr: Option<String>,
     min_next_bid_increment_rate: Option<Decimal>,
-    min_return: Option<Decimal>,
+    bid_window: Option<u64>,
+    lock_period_seconds: Option<u64>,
+    rebid_buffer: Option<Decimal>,
+    keeper_min_bond: Option<Uint128>,
+    keeper_unbonding_period: Option<u64>,
+    settlement_challenge_period: Option<u64>,
+    settlement_quorum: Option<u32>,
+    min_deposit: Option<Uint128>,
 ) -> Result<Response, ContractError> {
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, &env, &info.sender, &Role::ConfigAdmin)?;
 
     let mut config = CONFIG.load(deps.storage)?;
 
-    if let Some(rewards_fee) = rewards_fee {
-        config.rewards_fee = validate_percentage(rewards_fee)?;
+    if let Some(min_next_bid_increment_rate) = min_next_bid_increment_rate {
+        config.min_next_bid_increment_rate = validate_percentage(min_next_bid_increment_rate)?;
+    }
+
+    if let Some(bid_window) = bid_window {
+        config.bid_window = bid_window;
     }
 
-    if let Some(rewards_fee_addr) = rewards_fee_addr {
-        config.rewards_fee_addr = deps.api.addr_validate(&rewards_fee_addr)?;
+    if let Some(lock_period_seconds) = lock_period_seconds {
+        config.lock_period_seconds = lock_period_seconds;
     }
 
-    if let Some(min_next_bid_increment_rate) = min_next_bid_increment_rate {
-        config.min_next_bid_increment_rate = validate_percentage(min_next_bid_increment_rate)?;
+    if let Some(rebid_buffer) = rebid_buffer {
+        config.rebid_buffer = rebid_buffer;
+    }
+
+    if let Some(keeper_min_bond) = keeper_min_bond {
+        config.keeper_min_bond = keeper_min_bond;
+    }
+
+    if let Some(keeper_unbonding_period) = keeper_unbonding_period {
+        config.keeper_unbonding_period = keeper_unbonding_period;
+    }
+
+    if let Some(settlement_challenge_period) = settlement_challenge_period {
+        config.settlement_challenge_period = settlement_challenge_period;
+    }
+
+    if let Some(settlement_quorum) = settlement_quorum {
+        config.settlement_quorum = settlement_quorum;
     }
 
-    if let Some(min_return) = min_return {
-        config.min_return = validate_percentage(min_return)?;
+    if let Some(min_deposit) = min_deposit {
+        config.min_deposit = min_deposit;
     }
 
+    validate_keeper_unbonding_period(deps.as_ref(), &config)?;
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::default()
@@ -57,17 +102,186 @@ pub fn update_config(
             config.min_next_bid_increment_rate.to_string(),
         )
         .add_attribute("treasury_chest_code_id", config.treasury_chest_code_id.to_string())
-        .add_attribute("min_return", config.min_return.to_string()))
+        .add_attribute("min_return", config.min_return.to_string())
+        .add_attribute("price_sources", config.pricing.price_sources.len().to_string())
+        .add_attribute("max_price_age", config.pricing.max_price_age.to_string())
+        .add_attribute("basket_value_tolerance", config.pricing.basket_value_tolerance.to_string())
+        .add_attribute("bid_strategy", config.bid_strategy.to_string())
+        .add_attribute("bid_window", config.bid_window.to_string())
+        .add_attribute("lock_period_seconds", config.lock_period_seconds.to_string())
+        .add_attribute("rebid_buffer", config.rebid_buffer.to_string())
+        .add_attribute("exit_fee_base", config.exit_fee.base_fee.to_string())
+        .add_attribute("exit_fee_max", config.exit_fee.max_fee.to_string())
+        .add_attribute("exit_fee_ramp_seconds", config.exit_fee.ramp_seconds.to_string())
+        .add_attribute("surplus_policy", config.surplus_policy.to_string())
+        .add_attribute("config_timelock", config.config_timelock.to_string())
+        .add_attribute("keeper_min_bond", config.keeper_min_bond.to_string())
+        .add_attribute("keeper_unbonding_period", config.keeper_unbonding_period.to_string())
+        .add_attribute(
+            "settlement_challenge_period",
+            config.settlement_challenge_period.to_string(),
+        )
+        .add_attribute("settlement_quorum", config.settlement_quorum.to_string())
+        .add_attribute("min_deposit", config.min_deposit.to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn propose_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rewards_fee: Option<Decimal>,
+    rewards_fee_addr: Option<String>,
+    min_return: Option<Decimal>,
+    pricing: Option<PricingConfig>,
+    bid_strategy: Option<BidStrategy>,
+    exit_fee: Option<ExitFeeSchedule>,
+    surplus_policy: Option<SurplusPolicy>,
+    treasury_chest_code_id: Option<u64>,
+    config_timelock: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &env, &info.sender, &Role::ConfigAdmin)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let rewards_fee = rewards_fee.map(validate_percentage).transpose()?;
+    let rewards_fee_addr =
+        rewards_fee_addr.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    let min_return = min_return.map(validate_percentage).transpose()?;
+    let pricing = pricing.map(|pricing| validate_pricing(deps.api, pricing)).transpose()?;
+    let bid_strategy = bid_strategy.map(validate_bid_strategy).transpose()?;
+    let exit_fee = exit_fee.map(validate_exit_fee_schedule).transpose()?;
+    if let Some(code_id) = treasury_chest_code_id {
+        deps.querier.query_wasm_code_info(code_id)?;
+    }
+
+    // fee increases must wait for at least a full auction period, so depositors can leave the pool
+    // before they apply
+    let mut delay = config.config_timelock;
+    let raises_fee = rewards_fee.is_some_and(|rewards_fee| rewards_fee > config.rewards_fee)
+        || exit_fee.as_ref().is_some_and(|exit_fee| {
+            exit_fee.base_fee > config.exit_fee.base_fee
+                || exit_fee.max_fee > config.exit_fee.max_fee
+                || exit_fee.ramp_seconds > config.exit_fee.ramp_seconds
+        });
+    if raises_fee {
+        delay = delay.max(query_auction_period(deps.as_ref())?);
+    }
+
+    let proposed_at = env.block.time.seconds();
+    let activates_at = proposed_at.saturating_add(delay);
+    CONFIG_PROPOSAL.save(
+        deps.storage,
+        &ConfigProposal {
+            rewards_fee,
+            rewards_fee_addr,
+            min_return,
+            pricing,
+            bid_strategy,
+            exit_fee,
+            surplus_policy,
+            treasury_chest_code_id,
+            config_timelock,
+            proposed_at,
+            activates_at,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "propose_config")
+        .add_attribute("activates_at", activates_at.to_string()))
+}
+
+pub fn apply_config(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &env, &info.sender, &Role::ConfigAdmin)?;
+
+    let proposal =
+        CONFIG_PROPOSAL.may_load(deps.storage)?.ok_or(ContractError::NoConfigProposal {})?;
+    if env.block.time.seconds() < proposal.activates_at {
+        return Err(ContractError::ConfigProposalNotActive {
+            activates_at: proposal.activates_at,
+        });
+    }
+    CONFIG_PROPOSAL.remove(deps.storage);
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if let Some(rewards_fee) = proposal.rewards_fee {
+        config.rewards_fee = rewards_fee;
+    }
+
+    if let Some(rewards_fee_addr) = proposal.rewards_fee_addr {
+        config.rewards_fee_addr = rewards_fee_addr;
+    }
+
+    if let Some(min_return) = proposal.min_return {
+        config.min_return = min_return;
+    }
+
+    if let Some(pricing) = proposal.pricing {
+        config.pricing = pricing;
+    }
+
+    if let Some(bid_strategy) = proposal.bid_strategy {
+        config.bid_strategy = bid_strategy;
+    }
+
+    if let Some(exit_fee) = proposal.exit_fee {
+        config.exit_fee = exit_fee;
+    }
+
+    if let Some(surplus_policy) = proposal.surplus_policy {
+        config.surplus_policy = surplus_policy;
+    }
+
+    if let Some(treasury_chest_code_id) = proposal.treasury_chest_code_id {
+        config.treasury_chest_code_id = treasury_chest_code_id;
+    }
+
+    if let Some(config_timelock) = proposal.config_timelock {
+        config.config_timelock = config_timelock;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "apply_config")
+        .add_attribute("rewards_fee", config.rewards_fee.to_string())
+        .add_attribute("rewards_fee_addr", config.rewards_fee_addr.to_string())
+        .add_attribute("min_return", config.min_return.to_string())
+        .add_attribute("price_sources", config.pricing.price_sources.len().to_string())
+        .add_attribute("bid_strategy", config.bid_strategy.to_string())
+        .add_attribute("exit_fee_base", config.exit_fee.base_fee.to_string())
+        .add_attribute("exit_fee_max", config.exit_fee.max_fee.to_string())
+        .add_attribute("exit_fee_ramp_seconds", config.exit_fee.ramp_seconds.to_string())
+        .add_attribute("surplus_policy", config.surplus_policy.to_string())
+        .add_attribute("treasury_chest_code_id", config.treasury_chest_code_id.to_string())
+        .add_attribute("config_timelock", config.config_timelock.to_string()))
+}
+
+pub fn cancel_config_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &env, &info.sender, &Role::ConfigAdmin)?;
+
+    if !CONFIG_PROPOSAL.exists(deps.storage) {
+        return Err(ContractError::NoConfigProposal {});
+    }
+    CONFIG_PROPOSAL.remove(deps.storage);
+
+    Ok(Response::default().add_attribute("action", "cancel_config_proposal"))
 }
 
 pub fn update_whitelisted_addresses(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     remove: Vec<String>,
     add: Vec<String>,
 ) -> Result<Response, ContractError> {
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, &env, &info.sender, &Role::KeeperManager)?;
 
     let mut added = vec![];
     for addr in add.clone().into_iter() {
@@ -101,63 +315,294 @@ pub fn update_whitelisted_addresses(
         .add_attributes(added))
 }
 
+pub fn update_pause_status(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    join: Option<bool>,
+    exit: Option<bool>,
+    bid: Option<bool>,
+    settle: Option<bool>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, &env, &info.sender, &Role::Pauser)?;
+
+    let mut pause_status = PAUSE_STATUS.may_load(deps.storage)?.unwrap_or_default();
+
+    if let Some(join) = join {
+        pause_status.join = join;
+    }
+
+    if let Some(exit) = exit {
+        pause_status.exit = exit;
+    }
+
+    if let Some(bid) = bid {
+        pause_status.bid = bid;
+    }
+
+    if let Some(settle) = settle {
+        pause_status.settle = settle;
+    }
+
+    PAUSE_STATUS.save(deps.storage, &pause_status)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_pause_status")
+        .add_attribute("join", pause_status.join.to_string())
+        .add_attribute("exit", pause_status.exit.to_string())
+        .add_attribute("bid", pause_status.bid.to_string())
+        .add_attribute("settle", pause_status.settle.to_string()))
+}
+
+pub fn grant_role(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+    expires_at: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_role_manager(deps.storage, &env, &info.sender, &role)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    ROLES.save(
+        deps.storage,
+        (role.to_string().as_str(), &address),
+        &RoleGrant {
+            expires_at,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role.to_string())
+        .add_attribute("address", address.to_string())
+        .add_attribute("expires_at", expires_at.map_or("never".to_string(), |at| at.to_string())))
+}
+
+pub fn revoke_role(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    assert_role_manager(deps.storage, &env, &info.sender, &role)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    let role_name = role.to_string();
+    if !ROLES.has(deps.storage, (role_name.as_str(), &address)) {
+        return Err(ContractError::RoleNotGranted {
+            role: role_name,
+            address: address.to_string(),
+        });
+    }
+    ROLES.remove(deps.storage, (role_name.as_str(), &address));
+
+    Ok(Response::default()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role_name)
+        .add_attribute("address", address.to_string()))
+}
+
+/// Fails if the sender cannot grant or revoke the role. Admin roles are managed by the owner, the
+/// other ones by the keeper managers
+fn assert_role_manager(
+    storage: &dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    role: &Role,
+) -> Result<(), ContractError> {
+    if role.is_admin() {
+        cw_ownable::assert_owner(storage, sender)?;
+        return Ok(());
+    }
+    assert_role(storage, env, sender, &Role::KeeperManager)
+}
+
 /// Joins the pool
 pub(crate) fn join_pool(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     auction_round: u64,
+    rounds: Option<u32>,
 ) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, "join", |status| status.join)?;
+
     let config = CONFIG.load(deps.storage)?;
     let amount = cw_utils::must_pay(&info, &config.native_denom)?;
 
-    let current_auction_round = query_current_auction(deps.as_ref())?.auction_round;
-
-    // prevents the user from joining the pool if the auction round is over
-    if auction_round != current_auction_round.u64() {
-        return Err(ContractError::InvalidAuctionRound {
-            current_auction_round: current_auction_round.u64(),
-            auction_round,
+    if rounds == Some(0) {
+        return Err(ContractError::InvalidCommitment {
+            reason: "rounds must be greater than zero".to_string(),
         });
     }
 
-    let unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
+    let current_auction_round = query_current_auction(deps.as_ref())?.auction_round.u64();
 
-    // if the current auction round is different from the unsettled auction round,
-    // prevent the user from joining the pool
-    if unsettled_auction.auction_round != current_auction_round.u64() {
-        return Err(ContractError::AuctionRoundNotSettled {
-            unsettled_auction_round: unsettled_auction.auction_round,
-            current_auction_round: current_auction_round.u64(),
+    let pending_auction_round = if auction_round > current_auction_round {
+        // deposits made for a later auction round are kept pending until that round starts
+        Some(auction_round)
+    } else {
+        // prevents the user from joining the pool if the auction round is over
+        if auction_round != current_auction_round {
+            return Err(ContractError::InvalidAuctionRound {
+                current_auction_round,
+                auction_round,
+            });
+        }
+
+        let unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
+
+        // if the current auction round is different from the unsettled auction round,
+        // prevent the user from joining the pool
+        if unsettled_auction.auction_round != current_auction_round {
+            return Err(ContractError::AuctionRoundNotSettled {
+                unsettled_auction_round: unsettled_auction.auction_round,
+                current_auction_round,
+            });
+        }
+
+        // deposits made once the contract has bid don't take part in the bid, so they are kept
+        // pending until the next auction round
+        if FUNDS_LOCKED.load(deps.storage)? {
+            Some(auction_round.checked_add(1).ok_or(ContractError::OverflowError(
+                OverflowError {
+                    operation: cosmwasm_std::OverflowOperation::Add,
+                    operand1: auction_round.to_string(),
+                    operand2: 1.to_string(),
+                },
+            ))?)
+        } else {
+            None
+        }
+    };
+
+    // the deposits kept pending or committed are processed on settlement, so dust is refused
+    if (pending_auction_round.is_some() || rounds.is_some()) && amount < config.min_deposit {
+        return Err(ContractError::BelowMinDeposit {
+            amount,
+            min_deposit: config.min_deposit,
         });
     }
 
+    // pending deposits are held outside of the bidding balance until activated on settlement
+    if let Some(pending_auction_round) = pending_auction_round {
+        if rounds.is_some() {
+            return Err(ContractError::InvalidCommitment {
+                reason: "pending deposits cannot be committed".to_string(),
+            });
+        }
+
+        add_pending_deposit(deps.storage, &info.sender, pending_auction_round, amount)?;
+
+        return Ok(Response::default().add_attributes(vec![
+            ("action", "join_pool".to_string()),
+            ("auction_round", auction_round.to_string()),
+            ("sender", info.sender.to_string()),
+            ("bid_amount", amount.to_string()),
+            ("pending_auction_round", pending_auction_round.to_string()),
+        ]));
+    }
+
+    let unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
+
+    // increase the balance that can be used for bidding, minting shares at the current share price
+    let shares = add_to_pool(deps.storage, amount)?;
+
     // mint the lp token and send it to the user
     let mut messages = vec![];
     let lp_denom =
         format!("factory/{}/auction.{}", env.contract.address, unsettled_auction.lp_subdenom);
 
-    messages.push(config.token_factory_type.mint(env.contract.address.clone(), &lp_denom, amount));
-
-    // send the minted lp token to the user address
-    messages.push(
-        BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: coins(amount.into(), lp_denom),
-        }
-        .into(),
-    );
-
-    // increase the balance that can be used for bidding
-    BIDDING_BALANCE
-        .update::<_, ContractError>(deps.storage, |balance| Ok(balance.checked_add(amount)?))?;
+    messages.push(config.token_factory_type.mint(env.contract.address.clone(), &lp_denom, shares));
 
-    Ok(Response::default().add_messages(messages).add_attributes(vec![
+    let mut attributes = vec![
         ("action", "join_pool".to_string()),
         ("auction_round", auction_round.to_string()),
         ("sender", info.sender.to_string()),
         ("bid_amount", amount.to_string()),
-    ]))
+    ];
+
+    match rounds {
+        // committed deposits keep their LP tokens escrowed until the commitment expires
+        Some(rounds) => {
+            // the commitments may still be renewed by the settlement queue
+            assert_settlement_queue_empty(deps.storage)?;
+
+            let last_auction_round = auction_round.checked_add(u64::from(rounds) - 1).ok_or(
+                ContractError::OverflowError(OverflowError {
+                    operation: cosmwasm_std::OverflowOperation::Add,
+                    operand1: auction_round.to_string(),
+                    operand2: (rounds - 1).to_string(),
+                }),
+            )?;
+            let mut commitment =
+                COMMITMENTS.may_load(deps.storage, &info.sender)?.unwrap_or(Commitment {
+                    lp_subdenom: unsettled_auction.lp_subdenom,
+                    amount: Uint128::zero(),
+                    last_auction_round,
+                });
+            COMMITMENT_EXPIRIES.remove(deps.storage, (commitment.last_auction_round, &info.sender));
+            commitment.amount = commitment.amount.checked_add(shares)?;
+            commitment.last_auction_round = commitment.last_auction_round.max(last_auction_round);
+            COMMITMENTS.save(deps.storage, &info.sender, &commitment)?;
+            COMMITMENT_EXPIRIES.save(
+                deps.storage,
+                (commitment.last_auction_round, &info.sender),
+                &Empty {},
+            )?;
+            let total_committed =
+                TOTAL_COMMITTED.may_load(deps.storage)?.unwrap_or_default().checked_add(shares)?;
+            TOTAL_COMMITTED.save(deps.storage, &total_committed)?;
+            attributes.push(("last_committed_round", commitment.last_auction_round.to_string()));
+        },
+        // send the minted lp token to the user address
+        None => messages.push(
+            BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(shares.into(), lp_denom),
+            }
+            .into(),
+        ),
+    }
+
+    // keep track of the user's deposit
+    record_deposit(
+        deps.storage,
+        &info.sender,
+        auction_round,
+        unsettled_auction.lp_subdenom,
+        amount,
+    )?;
+
+    Ok(Response::default().add_messages(messages).add_attributes(attributes))
+}
+
+/// Withdraws a pending deposit
+pub(crate) fn cancel_deposit(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    auction_round: u64,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    // the deposits activated on settlement are handed out by the settlement queue
+    assert_settlement_queue_empty(deps.storage)?;
+
+    let amount = remove_pending_deposit(deps.storage, &info.sender, auction_round)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(Response::default()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), config.native_denom),
+        })
+        .add_attribute("action", "cancel_deposit")
+        .add_attribute("auction_round", auction_round.to_string())
+        .add_attribute("amount", amount.to_string()))
 }
 
 /// Exits the pool if the time is before T-1 day from the end of the auction.
@@ -166,62 +611,206 @@ pub(crate) fn exit_pool(
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    let current_auction_round_response = query_current_auction(deps.as_ref())?;
+    assert_not_paused(deps.storage, "exit", |status| status.exit)?;
+
+    let unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
+    let lp_subdenom = unsettled_auction.lp_subdenom;
 
     //make sure the user sends a correct amount and denom to exit the pool
-    let lp_denom = format!(
-        "factory/{}/auction.{}",
-        env.contract.address,
-        UNSETTLED_AUCTION.load(deps.storage)?.lp_subdenom
-    );
+    let lp_denom = format!("factory/{}/auction.{}", env.contract.address, lp_subdenom);
     let amount = cw_utils::must_pay(&info, lp_denom.as_str())?;
 
-    // prevents the user from exiting the pool if the contract has already bid on the auction
-    if FUNDS_LOCKED.load(deps.storage)?
-        && env.block.time.seconds()
-            < current_auction_round_response.auction_closing_time.i64() as u64
-    {
+    // prevents the user from exiting the pool if the auction is about to close
+    let lock_status = lock_status(deps.as_ref(), &env)?;
+    if lock_status.time_locked {
         return Err(ContractError::PooledAuctionLocked);
     }
 
-    // subtract the amount of INJ to send from the bidding balance
-    BIDDING_BALANCE
-        .update::<_, ContractError>(deps.storage, |balance| Ok(balance.checked_sub(amount)?))?;
+    // once the contract has bid on the auction, only the share of the unbid surplus can be
+    // withdrawn, returning the rest of the LP tokens
+    let (nav, lp_supply) = pool_nav(deps.storage)?;
+    let (shares, returned) = if lock_status.bid_locked {
+        let withdrawable = if nav.is_zero() {
+            Uint128::zero()
+        } else {
+            amount.multiply_ratio(withdrawable_surplus(deps.as_ref())?, nav)
+        };
+        if withdrawable.is_zero() {
+            return Err(ContractError::PooledAuctionLocked);
+        }
+        (withdrawable, amount.checked_sub(withdrawable)?)
+    } else {
+        (amount, Uint128::zero())
+    };
+    let amount = shares_value(shares, nav, lp_supply);
 
+    // the exit fee stays in the bidding balance for the remaining depositors
     let config = CONFIG.load(deps.storage)?;
+    let exit_fee =
+        amount.checked_mul_ceil(exit_fee_rate(&config, &env, unsettled_auction.closing_time)?)?;
+    let refund = amount.checked_sub(exit_fee)?;
+
+    // subtract the amount of INJ to send from the bidding balance
+    remove_from_pool(deps.storage, shares, refund)?;
+
+    // remove the withdrawn amount from the user's deposits
+    release_deposits(deps.storage, &info.sender, lp_subdenom, amount)?;
 
     // burn the LP token and send the inj back to the user
     let mut messages = vec![config.token_factory_type.burn(
         env.contract.address.clone(),
         lp_denom.as_str(),
-        amount,
+        shares,
     )];
 
-    messages.push(
-        BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: coins(amount.into(), config.native_denom.clone()),
-        }
-        .into(),
-    );
+    if !refund.is_zero() {
+        messages.push(
+            BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(refund.into(), config.native_denom.clone()),
+            }
+            .into(),
+        );
+    }
+
+    let mut attributes =
+        vec![("action", "exit_pool".to_string()), ("exit_fee", exit_fee.to_string())];
+    if !returned.is_zero() {
+        messages.push(
+            BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(returned.into(), lp_denom.as_str()),
+            }
+            .into(),
+        );
+        attributes.push(("withdrawn", amount.to_string()));
+        attributes.push(("returned", returned.to_string()));
+    }
+
+    Ok(Response::default().add_messages(messages).add_attributes(attributes))
+}
+
+pub(crate) fn request_exit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, "exit", |status| status.exit)?;
+
+    // there is no need to wait for the settlement if the pool is not locked
+    if !lock_status(deps.as_ref(), &env)?.is_locked {
+        return exit_pool(deps, env, info);
+    }
+
+    let unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
+
+    //make sure the user sends a correct amount and denom to exit the pool
+    let lp_denom =
+        format!("factory/{}/auction.{}", env.contract.address, unsettled_auction.lp_subdenom);
+    let amount = cw_utils::must_pay(&info, lp_denom.as_str())?;
+
+    // the pending exits are processed on settlement, so dust is refused
+    let config = CONFIG.load(deps.storage)?;
+    if amount < config.min_deposit {
+        return Err(ContractError::BelowMinDeposit {
+            amount,
+            min_deposit: config.min_deposit,
+        });
+    }
+    assert_settlement_queue_empty(deps.storage)?;
+
+    // the exit fee is charged at the rate of the time of the request, and kept in the bidding
+    // balance for the remaining depositors if the exit is refunded
+    let fee_shares =
+        amount.checked_mul_ceil(exit_fee_rate(&config, &env, unsettled_auction.closing_time)?)?;
+
+    // escrow the LP tokens until the auction is settled
+    let pending_exit =
+        PENDING_EXITS.update::<_, ContractError>(deps.storage, &info.sender, |pending_exit| {
+            let mut pending_exit = pending_exit.unwrap_or(PendingExit {
+                auction_round: unsettled_auction.auction_round,
+                lp_subdenom: unsettled_auction.lp_subdenom,
+                amount: Uint128::zero(),
+                fee_shares: Uint128::zero(),
+            });
+            pending_exit.amount = pending_exit.amount.checked_add(amount)?;
+            pending_exit.fee_shares = pending_exit.fee_shares.checked_add(fee_shares)?;
+            Ok(pending_exit)
+        })?;
+    let total_pending_exits =
+        TOTAL_PENDING_EXITS.may_load(deps.storage)?.unwrap_or_default().checked_add(amount)?;
+    TOTAL_PENDING_EXITS.save(deps.storage, &total_pending_exits)?;
+    let total_exit_fees = TOTAL_PENDING_EXIT_FEES
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_add(fee_shares)?;
+    TOTAL_PENDING_EXIT_FEES.save(deps.storage, &total_exit_fees)?;
 
     Ok(Response::default()
-        .add_messages(messages)
-        .add_attributes(vec![("action", "exit_pool".to_string())]))
+        .add_attribute("action", "request_exit")
+        .add_attribute("amount", pending_exit.amount.to_string())
+        .add_attribute("exit_fee_shares", pending_exit.fee_shares.to_string()))
+}
+
+pub(crate) fn cancel_exit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    // the exits of the auction round settled are handed out by the settlement queue
+    assert_settlement_queue_empty(deps.storage)?;
+
+    let pending_exit = PENDING_EXITS.may_load(deps.storage, &info.sender)?.ok_or(
+        ContractError::NoPendingExit {
+            address: info.sender.to_string(),
+        },
+    )?;
+    PENDING_EXITS.remove(deps.storage, &info.sender);
+    let total_pending_exits = TOTAL_PENDING_EXITS
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_sub(pending_exit.amount)?;
+    TOTAL_PENDING_EXITS.save(deps.storage, &total_pending_exits)?;
+    let total_exit_fees = TOTAL_PENDING_EXIT_FEES
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .checked_sub(pending_exit.fee_shares)?;
+    TOTAL_PENDING_EXIT_FEES.save(deps.storage, &total_exit_fees)?;
+
+    // return the escrowed LP tokens to the user
+    let lp_denom = format!("factory/{}/auction.{}", env.contract.address, pending_exit.lp_subdenom);
+
+    Ok(Response::default()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(pending_exit.amount.u128(), lp_denom),
+        })
+        .add_attribute("action", "cancel_exit")
+        .add_attribute("amount", pending_exit.amount.to_string()))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn try_bid(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     auction_round: u64,
-    basket_value: Uint128,
+    basket_value: Option<Uint128>,
+    max_bid: Option<Uint128>,
+    expected_highest_bid: Option<Uint128>,
+    slippage: Option<Decimal>,
 ) -> Result<Response, ContractError> {
     cw_utils::nonpayable(&info)?;
+    assert_not_paused(deps.storage, "bid", |status| status.bid)?;
 
-    // only whitelist addresses or the contract itself can bid on the auction
+    // only whitelist addresses or the contract itself can bid on the auction with their own basket
+    // value, otherwise the basket is valued on-chain
     let config = CONFIG.load(deps.storage)?;
-    if info.sender != env.contract.address && !WHITELISTED_ADDRESSES.has(deps.storage, &info.sender)
+    if basket_value.is_some()
+        && info.sender != env.contract.address
+        && !has_role(deps.storage, &env, &info.sender, &Role::Bidder)?
     {
         return Err(ContractError::Unauthorized {});
     }
@@ -237,6 +826,31 @@ pub(crate) fn try_bid(
         });
     }
 
+    // the basket grows during the round, so the snapshot of the unsettled auction round follows it
+    // for the settlement to measure every denom the contract receives
+    let mut unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
+    if unsettled_auction.auction_round == auction_round {
+        for coin in current_auction_round_response.amount.iter() {
+            match unsettled_auction.basket.iter_mut().find(|snapshot| snapshot.denom == coin.denom)
+            {
+                Some(snapshot) => snapshot.amount = coin.amount,
+                None => unsettled_auction.basket.push(coin.clone()),
+            }
+        }
+        UNSETTLED_AUCTION.save(deps.storage, &unsettled_auction)?;
+    }
+
+    // prevents the contract from bidding outside the bid window, to avoid being outbid and keeping
+    // the funds locked for longer than needed
+    let closing_time = current_auction_round_response.auction_closing_time.i64() as u64;
+    if config.bid_window > 0
+        && env.block.time.seconds() < closing_time.saturating_sub(config.bid_window)
+    {
+        return Ok(Response::default()
+            .add_attribute("action", "did_not_bid")
+            .add_attribute("reason", "outside_bid_window"));
+    }
+
     // prevents the contract from bidding if the contract is already the highest bidder
     if current_auction_round_response.highest_bidder == env.contract.address {
         return Ok(Response::default()
@@ -244,6 +858,46 @@ pub(crate) fn try_bid(
             .add_attribute("reason", "contract_is_already_the_highest_bidder"));
     }
 
+    // prevents the contract from bidding if the highest bid moved more than the caller accepted
+    // since the message was sent
+    if let Some(expected_highest_bid) = expected_highest_bid {
+        let accepted_highest_bid = expected_highest_bid
+            .checked_mul_floor(Decimal::one().checked_add(slippage.unwrap_or_default())?)?;
+        if current_auction_round_response.highest_bid_amount > accepted_highest_bid {
+            return Ok(Response::default()
+                .add_attribute("action", "did_not_bid")
+                .add_attribute("reason", "highest_bid_moved_beyond_slippage"));
+        }
+    }
+
+    // value the basket on-chain if there are price sources, keeping the lowest of both values
+    let basket_value = if config.pricing.price_sources.is_empty() {
+        basket_value.ok_or(ContractError::BasketValueRequired)?
+    } else {
+        let on_chain_value = query_basket_value(
+            deps.as_ref(),
+            &env,
+            &config,
+            &current_auction_round_response.amount,
+        )?;
+        match basket_value {
+            Some(basket_value) => {
+                check_basket_value(&config.pricing, basket_value, on_chain_value)?;
+                basket_value.min(on_chain_value)
+            },
+            None => {
+                if let Some(denom) =
+                    spot_priced_denom(&config, &current_auction_round_response.amount)
+                {
+                    return Err(ContractError::SpotPriceRequiresBasketValue {
+                        denom,
+                    });
+                }
+                on_chain_value
+            },
+        }
+    };
+
     // calculate the minimum allowed bid to not be rejected by the auction module
     // minimum_allowed_bid = (highest_bid_amount * (1 + min_next_bid_increment_rate)) + 1
     // the latest + 1 is to make sure the auction module accepts the bid all the times
@@ -261,17 +915,36 @@ pub(crate) fn try_bid(
     }
 
     // prevents the contract from bidding if the returns are not high enough
-    if basket_value * (Decimal::one() - config.min_return) < minimum_allowed_bid {
+    let maximum_allowed_bid = basket_value * (Decimal::one() - config.min_return);
+    if maximum_allowed_bid < minimum_allowed_bid {
         return Ok(Response::default()
             .add_attribute("action", "did_not_bid")
             .add_attribute("reason", "basket_value_is_not_worth_bidding_for"));
     }
 
+    // prevents the contract from bidding more than the caller approved
+    let maximum_allowed_bid = match max_bid {
+        Some(max_bid) if max_bid < minimum_allowed_bid => {
+            return Ok(Response::default()
+                .add_attribute("action", "did_not_bid")
+                .add_attribute("reason", "minimum_allowed_bid_is_higher_than_max_bid"));
+        },
+        Some(max_bid) => maximum_allowed_bid.min(max_bid),
+        None => maximum_allowed_bid,
+    };
+
+    // pick the bid amount within the allowed range following the bid strategy
+    let bid_amount = compute_bid_amount(
+        &config.bid_strategy,
+        minimum_allowed_bid,
+        maximum_allowed_bid.min(bidding_balance),
+    )?;
+
     let msg = <MsgBid as Into<CosmosMsg>>::into(MsgBid {
         sender: env.contract.address.to_string(),
         bid_amount: Some(Coin {
             denom: config.native_denom,
-            amount: minimum_allowed_bid.to_string(),
+            amount: bid_amount.to_string(),
         }),
         round: auction_round,
     });
@@ -279,10 +952,25 @@ pub(crate) fn try_bid(
     // lock the funds to prevent users from exiting the pool
     FUNDS_LOCKED.save(deps.storage, &true)?;
 
+    // keep track of the bid for the round history
+    let mut bids = ROUND_BIDS.may_load(deps.storage, auction_round)?.unwrap_or_default();
+    if bids.is_empty() {
+        let mut stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+        stats.rounds_participated += 1;
+        STATS.save(deps.storage, &stats)?;
+    }
+    bids.push(RoundBid {
+        amount: bid_amount,
+        height: env.block.height,
+        time: env.block.time.seconds(),
+    });
+    ROUND_BIDS.save(deps.storage, auction_round, &bids)?;
+
     Ok(Response::default()
         .add_message(msg)
         .add_attribute("action", "try_bid".to_string())
-        .add_attribute("amount", minimum_allowed_bid.to_string()))
+        .add_attribute("amount", bid_amount.to_string())
+        .add_attribute("bid_strategy", config.bid_strategy.to_string()))
 }
 
 pub fn settle_auction(
@@ -293,11 +981,15 @@ pub fn settle_auction(
     auction_winner: String,
     auction_winning_bid: Uint128,
 ) -> Result<Response, ContractError> {
-    // only whitelist addresses can settle the auction for now until the
+    assert_not_paused(deps.storage, "settle", |status| status.settle)?;
+    assert_no_attestation_required(deps.storage)?;
+
+    // only settlers can settle the auction for now until the
     // contract can query the aunction module for a specific auction round
-    if !WHITELISTED_ADDRESSES.has(deps.storage, &info.sender) {
-        return Err(ContractError::Unauthorized {});
-    }
+    assert_role(deps.storage, &env, &info.sender, &Role::Settler)?;
+
+    // settlers must be bonded, so that a wrong report can be slashed
+    assert_keeper_bonded(deps.storage, &info.sender)?;
 
     // prevents the contract from settling the wrong auction round
     let unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
@@ -318,17 +1010,99 @@ pub fn settle_auction(
         return Err(ContractError::AuctionRoundHasNotFinished);
     }
 
-    FUNDS_LOCKED.save(deps.storage, &false)?;
+    if PENDING_SETTLEMENT.exists(deps.storage) {
+        return Err(ContractError::SettlementPending {
+            auction_round,
+        });
+    }
 
-    let (messages, attributes) = new_auction_round(
-        deps,
-        &env,
-        info,
-        Some(auction_winner),
-        Some(auction_winning_bid),
-        unsettled_auction.basket,
+    // the auction is only settled once enough keepers have reported the same result
+    let config = CONFIG.load(deps.storage)?;
+    let quorum = config.settlement_quorum.max(1) as usize;
+    let mut reporters = vec![];
+    if quorum > 1 {
+        KEEPER_REPORTS.save(
+            deps.storage,
+            (auction_round, &info.sender),
+            &KeeperReport {
+                auction_winner: auction_winner.clone(),
+                auction_winning_bid,
+                reported_at: env.block.time.seconds(),
+            },
+        )?;
+
+        let matching_keepers = KEEPER_REPORTS
+            .prefix(auction_round)
+            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+            .filter(|item| match item {
+                Ok((_, report)) => {
+                    report.auction_winner == auction_winner
+                        && report.auction_winning_bid == auction_winning_bid
+                },
+                Err(_) => true,
+            })
+            .map(|item| item.map(|(keeper, _)| keeper))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        // the reports of keepers whose settler role was revoked or expired no longer count
+        for keeper in matching_keepers {
+            if has_role(deps.storage, &env, &keeper, &Role::Settler)? {
+                reporters.push(keeper);
+            }
+        }
+        let matching_reports = reporters.len();
+
+        if matching_reports < quorum {
+            return Ok(Response::default()
+                .add_attribute("action", "settle_auction")
+                .add_attribute("reported_auction_round", auction_round.to_string())
+                .add_attribute("matching_reports", matching_reports.to_string())
+                .add_attribute("settlement_quorum", quorum.to_string()));
+        }
+    }
+
+    // keep the report, so that it can be checked against the auction result later on
+    reporters.retain(|keeper| *keeper != info.sender);
+    SETTLEMENT_REPORTS.save(
+        deps.storage,
+        auction_round,
+        &SettlementReport {
+            keeper: info.sender.clone(),
+            auction_winner: auction_winner.clone(),
+            auction_winning_bid,
+            reported_at: env.block.time.seconds(),
+            slashed: false,
+            dispute: None,
+            reporters,
+        },
     )?;
 
+    // the report can be disputed during the challenge period before the auction is settled
+    let challenge_period = config.settlement_challenge_period;
+    if challenge_period > 0 {
+        let finalizes_at = env.block.time.seconds().saturating_add(challenge_period);
+        PENDING_SETTLEMENT.save(
+            deps.storage,
+            &PendingSettlement {
+                auction_round,
+                keeper: info.sender,
+                auction_winner,
+                auction_winning_bid,
+                reported_at: env.block.time.seconds(),
+                finalizes_at,
+                dispute: None,
+            },
+        )?;
+
+        return Ok(Response::default()
+            .add_attribute("action", "settle_auction")
+            .add_attribute("pending_auction_round", auction_round.to_string())
+            .add_attribute("finalizes_at", finalizes_at.to_string()));
+    }
+
+    let (messages, attributes) =
+        finalize_settlement(deps, &env, info, auction_winner, auction_winning_bid)?;
+
     Ok(Response::default()
         .add_attribute("action", "settle_auction")
         .add_messages(messages)
@@ -341,6 +1115,9 @@ pub fn try_settle_auction(
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, "settle", |status| status.settle)?;
+    assert_no_attestation_required(deps.storage)?;
+
     let unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
 
     let latest_auction_result_response = query_latest_auction_result(deps.as_ref())?
@@ -355,20 +1132,749 @@ pub fn try_settle_auction(
         });
     }
 
-    FUNDS_LOCKED.save(deps.storage, &false)?;
+    let auction_winning_bid: Uint128 = latest_auction_result_response.amount.parse()?;
 
-    let (messages, attributes) = new_auction_round(
+    // the auction module confirms or overrides the pending settlement, resolving any dispute
+    let mut response = Response::default()
+        .add_attribute("action", "try_settle_auction")
+        .add_attribute("auction_round", unsettled_auction.auction_round.to_string());
+    if let Some(pending) = PENDING_SETTLEMENT.may_load(deps.storage)? {
+        let confirmed = pending.auction_winner == latest_auction_result_response.winner
+            && pending.auction_winning_bid == auction_winning_bid;
+        response = response.add_attribute(
+            "pending_settlement",
+            if confirmed {
+                "confirmed"
+            } else {
+                "overridden"
+            },
+        );
+    }
+
+    let (messages, attributes) = finalize_settlement(
         deps,
         &env,
         info,
-        Some(latest_auction_result_response.winner),
-        Some(latest_auction_result_response.amount.parse()?),
+        latest_auction_result_response.winner,
+        auction_winning_bid,
+    )?;
+
+    Ok(response.add_messages(messages).add_attributes(attributes))
+}
+
+/// Disputes the pending settlement with a contradicting auction result
+pub fn dispute_settlement(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_winner: String,
+    auction_winning_bid: Uint128,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, "settle", |status| status.settle)?;
+
+    // only bonded settlers can dispute, so that disputes can't be spammed
+    assert_role(deps.storage, &env, &info.sender, &Role::Settler)?;
+    assert_keeper_bonded(deps.storage, &info.sender)?;
+
+    let mut pending =
+        PENDING_SETTLEMENT.may_load(deps.storage)?.ok_or(ContractError::NoPendingSettlement {})?;
+
+    if pending.dispute.is_some() {
+        return Err(ContractError::SettlementDisputed {
+            auction_round: pending.auction_round,
+        });
+    }
+
+    if env.block.time.seconds() >= pending.finalizes_at {
+        return Err(ContractError::DisputeWindowClosed {
+            finalizes_at: pending.finalizes_at,
+        });
+    }
+
+    if pending.auction_winner == auction_winner
+        && pending.auction_winning_bid == auction_winning_bid
+    {
+        return Err(ContractError::DisputeMatchesSettlement {});
+    }
+
+    let dispute = SettlementDispute {
+        keeper: info.sender.clone(),
+        auction_winner,
+        auction_winning_bid,
+        disputed_at: env.block.time.seconds(),
+    };
+    pending.dispute = Some(dispute.clone());
+    PENDING_SETTLEMENT.save(deps.storage, &pending)?;
+
+    // the disputing keeper can be slashed as well if the report turns out to be right
+    let mut report = SETTLEMENT_REPORTS.load(deps.storage, pending.auction_round)?;
+    report.dispute = Some(dispute);
+    SETTLEMENT_REPORTS.save(deps.storage, pending.auction_round, &report)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "dispute_settlement")
+        .add_attribute("auction_round", pending.auction_round.to_string())
+        .add_attribute("keeper", info.sender.to_string()))
+}
+
+/// Finalizes the pending settlement once the challenge period has passed without disputes
+pub fn finalize_pending_settlement(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, "settle", |status| status.settle)?;
+
+    let pending =
+        PENDING_SETTLEMENT.may_load(deps.storage)?.ok_or(ContractError::NoPendingSettlement {})?;
+
+    // a disputed settlement is only resolved by the owner or the auction module
+    if pending.dispute.is_some() {
+        return Err(ContractError::SettlementDisputed {
+            auction_round: pending.auction_round,
+        });
+    }
+
+    if env.block.time.seconds() < pending.finalizes_at {
+        return Err(ContractError::SettlementChallengePeriodActive {
+            finalizes_at: pending.finalizes_at,
+        });
+    }
+
+    let (messages, attributes) =
+        finalize_settlement(deps, &env, info, pending.auction_winner, pending.auction_winning_bid)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "finalize_settlement")
+        .add_attribute("auction_round", pending.auction_round.to_string())
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// Resolves a disputed pending settlement with the result given by the owner
+pub fn resolve_settlement_dispute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_winner: String,
+    auction_winning_bid: Uint128,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let pending =
+        PENDING_SETTLEMENT.may_load(deps.storage)?.ok_or(ContractError::NoPendingSettlement {})?;
+
+    if pending.dispute.is_none() {
+        return Err(ContractError::SettlementNotDisputed {
+            auction_round: pending.auction_round,
+        });
+    }
+
+    let (messages, attributes) =
+        finalize_settlement(deps, &env, info, auction_winner, auction_winning_bid)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "resolve_settlement_dispute")
+        .add_attribute("auction_round", pending.auction_round.to_string())
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// Settles the unsettled auction with the given result, starting a new auction round
+fn finalize_settlement(
+    mut deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    auction_winner: String,
+    auction_winning_bid: Uint128,
+) -> Result<(Vec<CosmosMsg>, Vec<Attribute>), ContractError> {
+    let unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
+
+    // the reports of the keepers are no longer needed once the round is settled
+    clear_keeper_reports(deps.storage, unsettled_auction.auction_round)?;
+
+    PENDING_SETTLEMENT.remove(deps.storage);
+    FUNDS_LOCKED.save(deps.storage, &false)?;
+
+    let (messages, attributes) = new_auction_round(
+        deps.branch(),
+        env,
+        info,
+        Some(auction_winner),
+        Some(auction_winning_bid),
         unsettled_auction.basket,
     )?;
 
+    // bids made on later auction rounds keep the funds locked
+    if !ROUND_BIDS.is_empty(deps.storage) {
+        FUNDS_LOCKED.save(deps.storage, &true)?;
+    }
+
+    Ok((messages, attributes))
+}
+
+/// Fails while a missed auction round the contract bid on waits for the owner's attestation, as it
+/// is the unsettled auction round until then
+fn assert_no_attestation_required(storage: &dyn Storage) -> Result<(), ContractError> {
+    if let Some(auction_round) = ATTESTATION_REQUIRED.may_load(storage)? {
+        return Err(ContractError::AttestationRequired {
+            auction_round,
+        });
+    }
+
+    Ok(())
+}
+
+/// Removes the reports of the keepers on the auction rounds up to the given one, including the
+/// missed auction rounds settled without them
+fn clear_keeper_reports(storage: &mut dyn Storage, auction_round: u64) -> StdResult<()> {
+    let reports = KEEPER_REPORTS
+        .prefix_range(
+            storage,
+            None,
+            Some(PrefixBound::inclusive(auction_round)),
+            cosmwasm_std::Order::Ascending,
+        )
+        .map(|item| item.map(|(key, _)| key))
+        .collect::<StdResult<Vec<_>>>()?;
+    for (auction_round, keeper) in reports {
+        KEEPER_REPORTS.remove(storage, (auction_round, &keeper));
+    }
+
+    Ok(())
+}
+
+/// Settles an unsettled auction round left behind by more than one auction round
+pub fn catch_up_settlement(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, "settle", |status| status.settle)?;
+    assert_no_attestation_required(deps.storage)?;
+
+    // a pending settlement is finalized or resolved first, so that its dispute isn't dropped
+    if let Some(pending) = PENDING_SETTLEMENT.may_load(deps.storage)? {
+        return Err(match pending.dispute {
+            Some(_) => ContractError::SettlementDisputed {
+                auction_round: pending.auction_round,
+            },
+            None => ContractError::SettlementPending {
+                auction_round: pending.auction_round,
+            },
+        });
+    }
+
+    let unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
+    let current_auction_round = query_current_auction(deps.as_ref())?.auction_round.u64();
+
+    // the previous auction round is settled with try_settle_auction or settle_auction
+    if current_auction_round <= unsettled_auction.auction_round + 1 {
+        return Err(ContractError::NoMissedAuctionRounds {
+            unsettled: unsettled_auction.auction_round,
+        });
+    }
+
+    // the contract can't win a round it never bid on, and the funds are only unlocked once every
+    // bid has been settled
+    let funds_locked = FUNDS_LOCKED.load(deps.storage)?;
+    let last_missed_round = current_auction_round - 1;
+    let mut missed_rounds = 0u64;
+    for auction_round in unsettled_auction.auction_round..=last_missed_round {
+        // only the basket of the unsettled auction round is known
+        let missed_auction = if auction_round == unsettled_auction.auction_round {
+            unsettled_auction.clone()
+        } else {
+            Auction {
+                basket: vec![],
+                auction_round,
+                lp_subdenom: unsettled_auction.lp_subdenom,
+                closing_time: 0,
+            }
+        };
+
+        if funds_locked && ROUND_BIDS.has(deps.storage, auction_round) {
+            clear_keeper_reports(deps.storage, auction_round)?;
+            UNSETTLED_AUCTION.save(deps.storage, &missed_auction)?;
+            ATTESTATION_REQUIRED.save(deps.storage, &auction_round)?;
+
+            return Ok(Response::default()
+                .add_attribute("action", "catch_up_settlement")
+                .add_attribute("missed_rounds_settled", missed_rounds.to_string())
+                .add_attribute("attestation_required", auction_round.to_string()));
+        }
+
+        if auction_round == last_missed_round {
+            break;
+        }
+
+        ROUND_HISTORY.save(
+            deps.storage,
+            auction_round,
+            &RoundRecord {
+                auction_round,
+                basket: missed_auction.basket,
+                winner: String::new(),
+                winning_bid: Uint128::zero(),
+                won: false,
+                bids: vec![],
+                fees: vec![],
+                lp_subdenom: unsettled_auction.lp_subdenom,
+                treasure_chest: None,
+                settled_at: env.block.time.seconds(),
+            },
+        )?;
+        missed_rounds += 1;
+    }
+
+    // the last missed round is settled as usual, starting the current auction round
+    let (auction_winner, auction_winning_bid) =
+        match query_latest_auction_result(deps.as_ref())?.last_auction_result {
+            Some(result) if result.round == last_missed_round => {
+                (result.winner, result.amount.parse::<Uint128>()?)
+            },
+            _ => (String::new(), Uint128::zero()),
+        };
+    if last_missed_round != unsettled_auction.auction_round {
+        UNSETTLED_AUCTION.save(
+            deps.storage,
+            &Auction {
+                basket: vec![],
+                auction_round: last_missed_round,
+                lp_subdenom: unsettled_auction.lp_subdenom,
+                closing_time: 0,
+            },
+        )?;
+    }
+
+    let (messages, attributes) =
+        finalize_settlement(deps, &env, info, auction_winner, auction_winning_bid)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "catch_up_settlement")
+        .add_attribute("missed_rounds_settled", missed_rounds.to_string())
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// Settles the missed auction round that needs an attestation with the result given by the owner
+#[allow(clippy::too_many_arguments)]
+pub fn attest_auction_round(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    auction_round: u64,
+    auction_winner: String,
+    auction_winning_bid: Uint128,
+    basket: Option<Vec<cosmwasm_std::Coin>>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    if ATTESTATION_REQUIRED.may_load(deps.storage)? != Some(auction_round) {
+        return Err(ContractError::NoAttestationRequired {
+            auction_round,
+        });
+    }
+    ATTESTATION_REQUIRED.remove(deps.storage);
+
+    if let Some(basket) = basket {
+        UNSETTLED_AUCTION.update::<_, ContractError>(deps.storage, |auction| {
+            Ok(Auction {
+                basket,
+                ..auction
+            })
+        })?;
+    }
+
+    let (messages, attributes) =
+        finalize_settlement(deps.branch(), &env, info, auction_winner, auction_winning_bid)?;
+
+    // the rounds missed after the attested one still have to be caught up. Their basket is unknown,
+    // while the closing time of the current auction round still applies to the exits
+    let current_auction = query_current_auction(deps.as_ref())?;
+    if current_auction.auction_round.u64() > auction_round + 1 {
+        UNSETTLED_AUCTION.update::<_, ContractError>(deps.storage, |auction| {
+            Ok(Auction {
+                basket: vec![],
+                auction_round: auction_round + 1,
+                closing_time: current_auction.auction_closing_time.i64() as u64,
+                ..auction
+            })
+        })?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "attest_auction_round")
+        .add_attribute("auction_round", auction_round.to_string())
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+/// Checks the keeper has bonded at least the minimum bond required to settle the auction
+fn assert_keeper_bonded(storage: &dyn Storage, keeper: &Addr) -> Result<(), ContractError> {
+    let min_bond = CONFIG.load(storage)?.keeper_min_bond;
+    if !min_bond.is_zero() {
+        let bond = KEEPER_BONDS.may_load(storage, keeper)?.unwrap_or_default();
+        if bond.amount < min_bond {
+            return Err(ContractError::InsufficientKeeperBond {
+                min_bond,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the auction round of the report of the keeper that has not settled the auction yet
+fn unsettled_report_round(storage: &dyn Storage, keeper: &Addr) -> StdResult<Option<u64>> {
+    if let Some(pending) = PENDING_SETTLEMENT.may_load(storage)? {
+        let disputed = pending.dispute.is_some_and(|dispute| dispute.keeper == keeper);
+        if pending.keeper == keeper || disputed {
+            return Ok(Some(pending.auction_round));
+        }
+    }
+
+    let auction_round = UNSETTLED_AUCTION.load(storage)?.auction_round;
+    if KEEPER_REPORTS.has(storage, (auction_round, keeper)) {
+        return Ok(Some(auction_round));
+    }
+
+    Ok(None)
+}
+
+/// Bonds the native denom sent by the keeper
+pub fn bond_keeper(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = cw_utils::must_pay(&info, &config.native_denom)?;
+
+    let bond = KEEPER_BONDS.update::<_, ContractError>(deps.storage, &info.sender, |bond| {
+        let mut bond = bond.unwrap_or(KeeperBond {
+            bonded_at: env.block.time.seconds(),
+            ..KeeperBond::default()
+        });
+        bond.amount = bond.amount.checked_add(amount)?;
+        Ok(bond)
+    })?;
+    let total_bonds =
+        TOTAL_KEEPER_BONDS.may_load(deps.storage)?.unwrap_or_default().checked_add(amount)?;
+    TOTAL_KEEPER_BONDS.save(deps.storage, &total_bonds)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "bond_keeper")
+        .add_attribute("keeper", info.sender.to_string())
+        .add_attribute("amount", bond.amount.to_string()))
+}
+
+/// Starts unbonding part of the keeper's bond
+pub fn unbond_keeper(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut bond =
+        KEEPER_BONDS.may_load(deps.storage, &info.sender)?.ok_or(ContractError::NoKeeperBond {
+            address: info.sender.to_string(),
+        })?;
+
+    // unbonding again restarts the unbonding period of the whole amount unbonding
+    let release_at = env.block.time.seconds().saturating_add(config.keeper_unbonding_period);
+    bond.amount = bond.amount.checked_sub(amount)?;
+    bond.unbonding = bond.unbonding.checked_add(amount)?;
+    bond.unbonding_release_at = Some(release_at);
+    KEEPER_BONDS.save(deps.storage, &info.sender, &bond)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "unbond_keeper")
+        .add_attribute("keeper", info.sender.to_string())
+        .add_attribute("unbonding", bond.unbonding.to_string())
+        .add_attribute("release_at", release_at.to_string()))
+}
+
+/// Sends the amount unbonded back to the keeper once the unbonding period has passed
+pub fn claim_keeper_bond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+
+    let mut bond = KEEPER_BONDS
+        .may_load(deps.storage, &info.sender)?
+        .filter(|bond| !bond.unbonding.is_zero())
+        .ok_or(ContractError::NoKeeperBond {
+            address: info.sender.to_string(),
+        })?;
+
+    let release_at = bond.unbonding_release_at.unwrap_or_default();
+    if env.block.time.seconds() < release_at {
+        return Err(ContractError::KeeperBondNotReleased {
+            release_at,
+        });
+    }
+
+    // the bond stays slashable while a report of the keeper has not settled the auction yet
+    if let Some(auction_round) = unsettled_report_round(deps.storage, &info.sender)? {
+        return Err(ContractError::KeeperBondFrozen {
+            auction_round,
+        });
+    }
+
+    let claimed = bond.unbonding;
+    bond.unbonding = Uint128::zero();
+    bond.unbonding_release_at = None;
+    if bond.amount.is_zero() {
+        KEEPER_BONDS.remove(deps.storage, &info.sender);
+    } else {
+        KEEPER_BONDS.save(deps.storage, &info.sender, &bond)?;
+    }
+    let total_bonds =
+        TOTAL_KEEPER_BONDS.may_load(deps.storage)?.unwrap_or_default().checked_sub(claimed)?;
+    TOTAL_KEEPER_BONDS.save(deps.storage, &total_bonds)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(Response::default()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(claimed.u128(), config.native_denom),
+        })
+        .add_attribute("action", "claim_keeper_bond")
+        .add_attribute("keeper", info.sender.to_string())
+        .add_attribute("amount", claimed.to_string()))
+}
+
+/// Slashes the keeper that settled the auction round if its report disagrees with the latest
+/// auction result, or else the keeper that disputed the report with a wrong result, compensating
+/// the depositors of the auction round with the whole bond
+pub fn slash_keeper(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    auction_round: u64,
+) -> Result<Response, ContractError> {
+    let mut report = SETTLEMENT_REPORTS.may_load(deps.storage, auction_round)?.ok_or(
+        ContractError::NoSettlementReport {
+            auction_round,
+        },
+    )?;
+    if report.slashed {
+        return Err(ContractError::ReportAlreadySlashed {
+            auction_round,
+        });
+    }
+
+    // the auction module only keeps the result of the latest auction round
+    let auction_result = query_latest_auction_result(deps.as_ref())?
+        .last_auction_result
+        .ok_or(ContractError::EmptyAuctionResult {})?;
+    if auction_result.round != auction_round {
+        return Err(ContractError::AuctionRoundMismatch {
+            unsettled: auction_round,
+            latest: auction_result.round,
+        });
+    }
+
+    let auction_winning_bid: Uint128 = auction_result.amount.parse()?;
+    let matches_result = |auction_winner: &String, winning_bid: Uint128| {
+        *auction_winner == auction_result.winner && winning_bid == auction_winning_bid
+    };
+    let (keeper, reporters, reported_winner, reported_winning_bid) =
+        if !matches_result(&report.auction_winner, report.auction_winning_bid) {
+            (
+                report.keeper.clone(),
+                report.reporters.clone(),
+                report.auction_winner.clone(),
+                report.auction_winning_bid,
+            )
+        } else {
+            match report.dispute.clone() {
+                Some(dispute)
+                    if !matches_result(&dispute.auction_winner, dispute.auction_winning_bid) =>
+                {
+                    (dispute.keeper, vec![], dispute.auction_winner, dispute.auction_winning_bid)
+                },
+                _ => {
+                    return Err(ContractError::ReportMatchesAuctionResult {
+                        auction_round,
+                    })
+                },
+            }
+        };
+
+    // the amount unbonding is slashed as well, and goes to the depositors of the auction round
+    let mut amount = Uint128::zero();
+    for keeper in std::iter::once(&keeper).chain(&reporters) {
+        let bond = KEEPER_BONDS.may_load(deps.storage, keeper)?.unwrap_or_default();
+        amount = amount.checked_add(bond.amount)?.checked_add(bond.unbonding)?;
+        KEEPER_BONDS.remove(deps.storage, keeper);
+    }
+    let total_bonds =
+        TOTAL_KEEPER_BONDS.may_load(deps.storage)?.unwrap_or_default().checked_sub(amount)?;
+    TOTAL_KEEPER_BONDS.save(deps.storage, &total_bonds)?;
+
+    let mut response = Response::default();
+    match compensated_treasure_chest(deps.as_ref(), &env, auction_round)? {
+        // the LP tokens of the auction round are redeemed from a treasure chest, so the bond is
+        // added to the rewards of the LP tokens not redeemed yet
+        Some(treasure_chest) if !amount.is_zero() => {
+            let config = CONFIG.load(deps.storage)?;
+            response = response
+                .add_message(WasmMsg::Execute {
+                    contract_addr: treasure_chest.to_string(),
+                    msg: to_json_binary(&treasurechest::chest::ExecuteMsg::AddRewards {})?,
+                    funds: coins(amount.u128(), config.native_denom),
+                })
+                .add_attribute("treasure_chest", treasure_chest.to_string());
+        },
+        // the LP tokens of the auction round are still the shares of the bidding balance
+        _ => {
+            let balance = BIDDING_BALANCE.load(deps.storage)?.checked_add(amount)?;
+            BIDDING_BALANCE.save(deps.storage, &balance)?;
+        },
+    }
+
+    report.slashed = true;
+    SETTLEMENT_REPORTS.save(deps.storage, auction_round, &report)?;
+    SLASHINGS.save(
+        deps.storage,
+        auction_round,
+        &Slashing {
+            auction_round,
+            keeper: keeper.clone(),
+            reporters: reporters.clone(),
+            amount,
+            reported_winner,
+            reported_winning_bid,
+            auction_winner: auction_result.winner,
+            auction_winning_bid,
+            slashed_at: env.block.time.seconds(),
+        },
+    )?;
+
+    Ok(response
+        .add_attribute("action", "slash_keeper")
+        .add_attribute("auction_round", auction_round.to_string())
+        .add_attribute("keeper", keeper.to_string())
+        .add_attributes(reporters.into_iter().map(|reporter| attr("reporter", reporter)))
+        .add_attribute("amount", amount.to_string()))
+}
+
+/// Returns the treasure chest redeeming the LP tokens the auction round was settled with, if they
+/// have been replaced by a won auction and some of them are not redeemed yet. Treasure chests
+/// instantiated from another code id than the configured one may not accept rewards, so they are
+/// never compensated
+fn compensated_treasure_chest(
+    deps: Deps,
+    env: &Env,
+    auction_round: u64,
+) -> Result<Option<Addr>, ContractError> {
+    let Some(record) = ROUND_HISTORY.may_load(deps.storage, auction_round)? else {
+        return Ok(None);
+    };
+
+    // the LP subdenom is replaced by the first auction won from the auction round on
+    let won_record = ROUND_HISTORY
+        .range(
+            deps.storage,
+            Some(Bound::inclusive(auction_round)),
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .find(|item| match item {
+            Ok((_, round)) => round.lp_subdenom == record.lp_subdenom && round.won,
+            Err(_) => true,
+        })
+        .transpose()?;
+    let Some(treasure_chest) = won_record.and_then(|(_, round)| round.treasure_chest) else {
+        return Ok(None);
+    };
+
+    let code_id = deps.querier.query_wasm_contract_info(treasure_chest.clone())?.code_id;
+    if code_id != CONFIG.load(deps.storage)?.treasury_chest_code_id {
+        return Ok(None);
+    }
+
+    let lp_denom = format!("factory/{}/auction.{}", env.contract.address, record.lp_subdenom);
+    let supply = deps.querier.query_supply(lp_denom.clone())?.amount;
+    let redeemed = deps.querier.query_balance(treasure_chest.clone(), lp_denom)?.amount;
+    if supply <= redeemed {
+        return Ok(None);
+    }
+
+    Ok(Some(treasure_chest))
+}
+
+/// Processes the entries left in the settlement queue
+pub fn process_settlement_queue_entries(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+    assert_not_paused(deps.storage, "settle", |status| status.settle)?;
+
+    if !SETTLEMENT_QUEUE.exists(deps.storage) {
+        return Err(ContractError::NoSettlementQueue);
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.map_or(SETTLEMENT_BATCH_SIZE, |limit| limit as usize);
+    let (messages, attributes) = process_settlement_queue(deps.storage, &env, &config, limit)?;
+
     Ok(Response::default()
-        .add_attribute("action", "try_settle_auction")
-        .add_attribute("auction_round", unsettled_auction.auction_round.to_string())
+        .add_attribute("action", "process_settlement_queue")
         .add_messages(messages)
         .add_attributes(attributes))
 }
+
+/// Sends the whole balance of a denom the contract doesn't account for to the recipient. The native
+/// denom, the LP tokens, the denoms of the unsettled auction basket and the rewards left in the
+/// settlement queue are owed by the contract
+pub fn sweep_untracked_funds(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    cw_utils::nonpayable(&info)?;
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let unsettled_auction = UNSETTLED_AUCTION.load(deps.storage)?;
+    let queued_rewards = SETTLEMENT_QUEUE
+        .may_load(deps.storage)?
+        .map(|queue| queue.commitment_rewards)
+        .unwrap_or_default();
+    if denom == config.native_denom
+        || denom.starts_with(&format!("factory/{}/", env.contract.address))
+        || unsettled_auction.basket.iter().any(|coin| coin.denom == denom)
+        || queued_rewards.iter().any(|coin| coin.denom == denom)
+    {
+        return Err(ContractError::TrackedDenom {
+            denom,
+        });
+    }
+
+    let balance = deps.querier.query_balance(&env.contract.address, &denom)?;
+    if balance.amount.is_zero() {
+        return Err(ContractError::NoUntrackedFunds {
+            denom,
+        });
+    }
+
+    Ok(Response::default()
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![balance.clone()],
+        })
+        .add_attribute("action", "sweep_untracked_funds")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", balance.to_string()))
+}
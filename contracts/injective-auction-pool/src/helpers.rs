@@ -1,18 +1,104 @@
 use std::str::FromStr;
 
 use cosmwasm_std::{
-    attr, instantiate2_address, to_json_binary, Addr, Attribute, BankMsg, Binary, CanonicalAddr,
-    CodeInfoResponse, Coin, CosmosMsg, CustomQuery, Decimal, Deps, DepsMut, Env, MessageInfo,
-    OverflowError, QueryRequest, StdResult, Uint128, WasmMsg,
+    attr, coins, instantiate2_address, to_json_binary, Addr, Attribute, BankMsg, Binary,
+    CanonicalAddr, CodeInfoResponse, Coin, CosmosMsg, CustomQuery, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, OverflowError, QueryRequest, StdError, StdResult, Storage, Uint128,
+    WasmMsg,
 };
+use cw_storage_plus::{Bound, PrefixBound};
 use cw_utils::must_pay;
-use injective_std::types::injective::auction::v1beta1::QueryLastAuctionResultResponse;
+use injective_auction::auction_pool::{
+    BidStrategy, Config, ExitFeeSchedule, LockStatusResponse, PauseStatus, Role, RoundRecord,
+    SettlementQueue, SurplusPolicy,
+};
+use injective_std::types::injective::auction::v1beta1::{
+    QueryAuctionParamsResponse, QueryLastAuctionResultResponse,
+};
 
 use crate::{
-    state::{Auction, BIDDING_BALANCE, CONFIG, TREASURE_CHEST_CONTRACTS, UNSETTLED_AUCTION},
+    state::{
+        Auction, Deposit, BIDDING_BALANCE, COMMITMENTS, COMMITMENT_EXPIRIES, CONFIG,
+        DEPOSITOR_TOTALS, DEPOSITS, FUNDS_LOCKED, LP_SUPPLY, PAUSE_STATUS, PENDING_DEPOSITS,
+        PENDING_DEPOSIT_TOTALS, PENDING_EXITS, ROLES, ROUND_BIDS, ROUND_DEPOSITORS, ROUND_HISTORY,
+        ROUND_PENDING_DEPOSITS, SETTLEMENT_QUEUE, STATS, TOTAL_COMMITTED, TOTAL_KEEPER_BONDS,
+        TOTAL_PENDING_DEPOSITS, TOTAL_PENDING_EXITS, TOTAL_PENDING_EXIT_FEES,
+        TREASURE_CHEST_CONTRACTS, UNSETTLED_AUCTION, WHITELISTED_ADDRESSES,
+    },
     ContractError,
 };
 
+/// Number of settlement queue entries processed along with the settlement
+pub(crate) const SETTLEMENT_BATCH_SIZE: usize = 30;
+
+/// Computes the basket received by the contract for winning the auction. Only the denoms of the
+/// snapshot basket are measured, from the balances of the contract minus the funds it owes in them:
+/// for the native denom, the bidding balance left, the keeper bonds, the pending deposits and the
+/// funds sent along with the settlement, less the bid the auction module holds in escrow for a
+/// later round. The LP tokens of the contract are never part of the basket
+pub(crate) fn received_basket(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    config: &Config,
+    snapshot_basket: &[Coin],
+    bidding_balance: Uint128,
+) -> Result<Vec<Coin>, ContractError> {
+    let keeper_bonds = TOTAL_KEEPER_BONDS.may_load(deps.storage)?.unwrap_or_default();
+    let pending_deposits = TOTAL_PENDING_DEPOSITS.may_load(deps.storage)?.unwrap_or_default();
+    let funds_sent = info
+        .funds
+        .iter()
+        .filter(|coin| coin.denom == config.native_denom)
+        .map(|coin| coin.amount)
+        .sum::<Uint128>();
+
+    // the bid of the contract on the current auction round left its balance but is still part of
+    // the bidding balance, until the contract is outbid and refunded
+    let current_auction = query_current_auction(deps)?;
+    let escrowed_bid = if current_auction.highest_bidder == env.contract.address {
+        current_auction.highest_bid_amount
+    } else {
+        Uint128::zero()
+    };
+
+    let liabilities = bidding_balance
+        .checked_add(keeper_bonds)?
+        .checked_add(pending_deposits)?
+        .checked_add(funds_sent)?
+        .saturating_sub(escrowed_bid);
+
+    let lp_denom_prefix = format!("factory/{}/", env.contract.address);
+    let mut basket: Vec<Coin> = vec![];
+    for coin in snapshot_basket {
+        if coin.denom.starts_with(&lp_denom_prefix)
+            || basket.iter().any(|received| received.denom == coin.denom)
+        {
+            continue;
+        }
+
+        let balance = deps.querier.query_balance(&env.contract.address, &coin.denom)?;
+        let amount = if coin.denom == config.native_denom {
+            balance.amount.saturating_sub(liabilities)
+        } else {
+            balance.amount
+        };
+        if !amount.is_zero() {
+            basket.push(Coin {
+                denom: coin.denom.clone(),
+                amount,
+            });
+        }
+    }
+
+    Ok(basket)
+}
+
+/// Formats the basket as a comma separated list of coins, for the attributes
+fn format_basket(basket: &[Coin]) -> String {
+    basket.iter().map(|coin| coin.to_string()).collect::<Vec<_>>().join(",")
+}
+
 pub fn predict_address<T: CustomQuery>(
     code_id: u64,
     label: &String,
@@ -77,14 +163,35 @@ pub(crate) fn new_auction_round(
 
     match unsettled_auction {
         Some(unsettled_auction) => {
+            // the entries left by the previous settlement must be processed first
+            assert_settlement_queue_empty(deps.storage)?;
+
             let auction_winner = auction_winner.ok_or(ContractError::MissingAuctionWinner {})?;
             let auction_winning_bid =
                 auction_winning_bid.ok_or(ContractError::MissingAuctionWinningBid {})?;
+
+            // the bids made on the settled round are moved to the round history
+            let bids = ROUND_BIDS
+                .may_load(deps.storage, unsettled_auction.auction_round)?
+                .unwrap_or_default();
+            ROUND_BIDS.remove(deps.storage, unsettled_auction.auction_round);
+
+            // bids are increasing, so the last one is the highest bid of the contract
+            let mut stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+            if let Some(bid) = bids.last() {
+                stats.total_bid = stats.total_bid.checked_add(bid.amount)?;
+            }
+
             // the contract won the auction
             // NOTE: this is assuming the bot is sending the correct data about the winner of the
             // previous auction currently there's no way to query the auction module
-            // directly to get this information
-            if deps.api.addr_validate(&auction_winner)? == env.contract.address {
+            // directly to get this information. The settlement quorum requires several keepers
+            // to agree on it, and a keeper reporting the wrong winner can be slashed once the
+            // auction result is available
+            // an auction round without bids has no winner
+            if !auction_winner.is_empty()
+                && deps.api.addr_validate(&auction_winner)? == env.contract.address
+            {
                 // update LP subdenom for the next auction round (increment by 1)
                 let new_subdenom = unsettled_auction.lp_subdenom.checked_add(1).ok_or(
                     ContractError::OverflowError(OverflowError {
@@ -95,20 +202,34 @@ pub(crate) fn new_auction_round(
                 )?;
 
                 let mut basket_fees = vec![];
-                let mut basket_to_treasure_chest = vec![];
+                let mut basket_rewards = vec![];
 
-                // add the unused bidding balance to the basket to be redeemed later
-                // TODO: should this be taxed though? if not, move after the for loop
-                let remaining_bidding_balance =
+                let mut queue = new_settlement_queue(
+                    unsettled_auction.auction_round,
+                    true,
+                    current_auction_round_response.auction_round.u64(),
+                    new_subdenom,
+                );
+
+                let mut remaining_bidding_balance =
                     BIDDING_BALANCE.load(deps.storage)?.checked_sub(auction_winning_bid)?;
+                let (_, lp_supply) = pool_nav(deps.storage)?;
 
-                // If there is a remaining bidding balance, add it to the basket
-                if remaining_bidding_balance > Uint128::zero() {
-                    basket_to_treasure_chest.push(Coin {
-                        denom: config.native_denom.clone(),
-                        amount: remaining_bidding_balance,
-                    });
+                // the basket usually grows during the round, so the snapshot taken when it started
+                // is replaced by what the contract actually received
+                let received_basket = received_basket(
+                    deps.as_ref(),
+                    env,
+                    &info,
+                    &config,
+                    &old_basket,
+                    remaining_bidding_balance,
+                )?;
+                if received_basket != old_basket {
+                    attributes.push(attr("snapshot_basket", format_basket(&old_basket)));
+                    attributes.push(attr("received_basket", format_basket(&received_basket)));
                 }
+                let old_basket = received_basket;
 
                 // Split the basket, taking the rewards fees into account
                 if old_basket.is_empty() {
@@ -127,23 +248,101 @@ pub(crate) fn new_auction_round(
                     let net_amount = coin.amount.checked_sub(fee)?;
                     if !net_amount.is_zero() {
                         add_coin_to_basket(
-                            &mut basket_to_treasure_chest,
+                            &mut basket_rewards,
                             Coin {
                                 denom: coin.denom.clone(),
                                 amount: net_amount,
                             },
-                        )?
+                        )?;
+                        add_coin_to_basket(
+                            &mut stats.total_distributed,
+                            Coin {
+                                denom: coin.denom.clone(),
+                                amount: net_amount,
+                            },
+                        )?;
+                    }
+                }
+
+                for fee in basket_fees.iter() {
+                    add_coin_to_basket(&mut stats.total_fees, fee.clone())?;
+                }
+                stats.rounds_won += 1;
+                stats.total_burned = stats.total_burned.checked_add(auction_winning_bid)?;
+                // the deposits are now redeemable from the treasure chest
+                stats.depositor_count = 0;
+
+                // the commitments take their share out before the rest goes to the chest
+                let commitment_burn = renew_commitments(
+                    deps.storage,
+                    env,
+                    &config,
+                    unsettled_auction.lp_subdenom,
+                    lp_supply,
+                    &mut remaining_bidding_balance,
+                    &mut basket_rewards,
+                    &mut queue,
+                )?;
+                let reentered = queue.reentered;
+
+                let mut basket_to_treasure_chest = vec![];
+
+                // If there is a remaining bidding balance, add it to the basket to be redeemed
+                // later. When rolling it over, the basket gets the matching LP tokens of the new
+                // round instead
+                // TODO: should this be taxed though?
+                let new_lp_denom =
+                    format!("factory/{}/auction.{}", env.contract.address, new_subdenom);
+                let mut rolled_over = Uint128::zero();
+                if remaining_bidding_balance > Uint128::zero() {
+                    match config.surplus_policy {
+                        SurplusPolicy::ToChest => {
+                            let surplus = Coin {
+                                denom: config.native_denom.clone(),
+                                amount: remaining_bidding_balance,
+                            };
+                            add_coin_to_basket(&mut stats.total_distributed, surplus.clone())?;
+                            basket_to_treasure_chest.push(surplus);
+                        },
+                        SurplusPolicy::RollOver => {
+                            basket_to_treasure_chest.push(Coin {
+                                denom: new_lp_denom.clone(),
+                                amount: remaining_bidding_balance,
+                            });
+                            rolled_over = remaining_bidding_balance;
+                        },
                     }
                 }
+                basket_to_treasure_chest
+                    .extend(basket_rewards.into_iter().filter(|coin| !coin.amount.is_zero()));
+                STATS.save(deps.storage, &stats)?;
 
-                // reset the bidding balance to 0 if we won, otherwise keep the balance for the next round
-                BIDDING_BALANCE.save(deps.storage, &Uint128::zero())?;
+                // the new LP tokens must exist before being sent to the treasure chest or escrowed
+                // for the commitments
+                let new_lp_supply = rolled_over.checked_add(reentered)?;
+                if !new_lp_supply.is_zero() {
+                    messages.push(config.token_factory_type.create_denom(
+                        env.contract.address.clone(),
+                        format!("auction.{}", new_subdenom).as_str(),
+                    ));
+                    messages.push(config.token_factory_type.mint(
+                        env.contract.address.clone(),
+                        &new_lp_denom,
+                        new_lp_supply,
+                    ));
+                }
+                messages.extend(commitment_burn);
+
+                // reset the bidding balance to 0 if we won, otherwise keep the balance for the next round.
+                // The balance rolled over and re-entered is backed 1:1 by the new LP tokens
+                BIDDING_BALANCE.save(deps.storage, &new_lp_supply)?;
+                LP_SUPPLY.save(deps.storage, &new_lp_supply)?;
 
                 // transfer corresponding tokens to the rewards fee address
                 if !basket_fees.is_empty() {
                     messages.push(CosmosMsg::Bank(BankMsg::Send {
                         to_address: config.rewards_fee_addr.to_string(),
-                        amount: basket_fees,
+                        amount: basket_fees.clone(),
                     }))
                 }
 
@@ -184,6 +383,23 @@ pub(crate) fn new_auction_round(
                     &treasure_chest_address,
                 )?;
 
+                ROUND_HISTORY.save(
+                    deps.storage,
+                    unsettled_auction.auction_round,
+                    &RoundRecord {
+                        auction_round: unsettled_auction.auction_round,
+                        basket: old_basket,
+                        winner: auction_winner,
+                        winning_bid: auction_winning_bid,
+                        won: true,
+                        bids,
+                        fees: basket_fees,
+                        lp_subdenom: unsettled_auction.lp_subdenom,
+                        treasure_chest: Some(treasure_chest_address.clone()),
+                        settled_at: env.block.time.seconds(),
+                    },
+                )?;
+
                 // transfer previous token factory's admin rights to the treasury chest contract
                 messages.push(config.token_factory_type.change_admin(
                     env.contract.address.clone(),
@@ -191,6 +407,16 @@ pub(crate) fn new_auction_round(
                     treasure_chest_address.clone(),
                 ));
 
+                // the escrowed LP tokens of the pending exits are redeemable from the treasure
+                // chest
+                queue_pending_exits(
+                    deps.storage,
+                    env,
+                    &config,
+                    unsettled_auction.lp_subdenom,
+                    &mut queue,
+                )?;
+
                 // create a new denom for the current auction round
                 let amount = must_pay(&info, &config.native_denom)?;
                 if amount < config.min_balance {
@@ -199,10 +425,15 @@ pub(crate) fn new_auction_round(
                         min_balance: config.min_balance,
                     });
                 }
-                messages.push(config.token_factory_type.create_denom(
-                    env.contract.address.clone(),
-                    format!("auction.{}", new_subdenom).as_str(),
-                ));
+                if new_lp_supply.is_zero() {
+                    messages.push(config.token_factory_type.create_denom(
+                        env.contract.address.clone(),
+                        format!("auction.{}", new_subdenom).as_str(),
+                    ));
+                }
+
+                // the pending deposits join the pool with the new LP subdenom
+                messages.extend(activate_pending_deposits(deps.storage, env, &config, &mut queue)?);
 
                 // save the current auction details to the contract state
                 UNSETTLED_AUCTION.save(
@@ -225,11 +456,64 @@ pub(crate) fn new_auction_round(
                 ));
                 attributes.push(attr("treasure_chest_address", treasure_chest_address.to_string()));
                 attributes.push(attr("new_subdenom", format!("auction.{}", new_subdenom)));
+                if !rolled_over.is_zero() {
+                    attributes.push(attr("rolled_over", rolled_over.to_string()));
+                }
+                if !reentered.is_zero() {
+                    attributes.push(attr("commitments_reentered", reentered.to_string()));
+                }
+
+                // the first entries of the settlement queue are processed right away
+                SETTLEMENT_QUEUE.save(deps.storage, &queue)?;
+                let (queue_messages, queue_attributes) =
+                    process_settlement_queue(deps.storage, env, &config, SETTLEMENT_BATCH_SIZE)?;
+                messages.extend(queue_messages);
+                attributes.extend(queue_attributes);
 
                 Ok((messages, attributes))
             }
             // the contract did NOT win the auction
             else {
+                STATS.save(deps.storage, &stats)?;
+
+                // the deposits of the expired commitments stay in the pool, handing out their LP
+                // tokens as the settlement queue is processed
+                let mut queue = new_settlement_queue(
+                    unsettled_auction.auction_round,
+                    false,
+                    current_auction_round_response.auction_round.u64(),
+                    unsettled_auction.lp_subdenom,
+                );
+
+                // refund the escrowed LP tokens, as the funds were not used
+                messages.extend(queue_pending_exits(
+                    deps.storage,
+                    env,
+                    &config,
+                    unsettled_auction.lp_subdenom,
+                    &mut queue,
+                )?);
+
+                // the pending deposits join the pool with the current LP subdenom
+                messages.extend(activate_pending_deposits(deps.storage, env, &config, &mut queue)?);
+
+                ROUND_HISTORY.save(
+                    deps.storage,
+                    unsettled_auction.auction_round,
+                    &RoundRecord {
+                        auction_round: unsettled_auction.auction_round,
+                        basket: old_basket,
+                        winner: auction_winner,
+                        winning_bid: auction_winning_bid,
+                        won: false,
+                        bids,
+                        fees: vec![],
+                        lp_subdenom: unsettled_auction.lp_subdenom,
+                        treasure_chest: None,
+                        settled_at: env.block.time.seconds(),
+                    },
+                )?;
+
                 // save the current auction details to the contract state, keeping the previous LP
                 // subdenom
                 UNSETTLED_AUCTION.save(
@@ -250,6 +534,14 @@ pub(crate) fn new_auction_round(
                     "new_auction_round",
                     current_auction_round_response.auction_round.to_string(),
                 ));
+
+                // the first entries of the settlement queue are processed right away
+                SETTLEMENT_QUEUE.save(deps.storage, &queue)?;
+                let (queue_messages, queue_attributes) =
+                    process_settlement_queue(deps.storage, env, &config, SETTLEMENT_BATCH_SIZE)?;
+                messages.extend(queue_messages);
+                attributes.extend(queue_attributes);
+
                 Ok((messages, attributes))
             }
         },
@@ -266,6 +558,7 @@ pub(crate) fn new_auction_round(
             )?;
 
             BIDDING_BALANCE.save(deps.storage, &Uint128::zero())?;
+            LP_SUPPLY.save(deps.storage, &Uint128::zero())?;
 
             // create a new denom for the current auction round
             messages.push(
@@ -282,6 +575,590 @@ pub(crate) fn new_auction_round(
     }
 }
 
+/// Starts the settlement queue of the auction round settled
+fn new_settlement_queue(
+    auction_round: u64,
+    won: bool,
+    new_auction_round: u64,
+    lp_subdenom: u64,
+) -> SettlementQueue {
+    SettlementQueue {
+        auction_round,
+        won,
+        new_auction_round,
+        lp_subdenom,
+        exit_shares: Uint128::zero(),
+        exit_refunds: Uint128::zero(),
+        committed: Uint128::zero(),
+        reentered: Uint128::zero(),
+        commitment_rewards: vec![],
+        commitments_after: None,
+        deposits: Uint128::zero(),
+        deposit_shares: Uint128::zero(),
+    }
+}
+
+/// Returns an error while the settlement queue is being processed
+pub(crate) fn assert_settlement_queue_empty(storage: &dyn Storage) -> Result<(), ContractError> {
+    match SETTLEMENT_QUEUE.may_load(storage)? {
+        Some(queue) => Err(ContractError::SettlementQueuePending {
+            auction_round: queue.auction_round,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Takes the exits requested while the pool was locked out of the pool. If the auction was lost,
+/// the escrowed LP tokens are burned and their value at the current share price is refunded as
+/// the settlement queue is processed, except for the exit fees that stay in the bidding balance.
+/// Returns the burn message, if any
+fn queue_pending_exits(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    lp_subdenom: u64,
+    queue: &mut SettlementQueue,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    let shares = TOTAL_PENDING_EXITS.may_load(storage)?.unwrap_or_default();
+    let fee_shares = TOTAL_PENDING_EXIT_FEES.may_load(storage)?.unwrap_or_default();
+    TOTAL_PENDING_EXITS.remove(storage);
+    TOTAL_PENDING_EXIT_FEES.remove(storage);
+    if queue.won || shares.is_zero() {
+        return Ok(None);
+    }
+
+    let (nav, lp_supply) = pool_nav(storage)?;
+    let net_shares = shares.checked_sub(fee_shares)?;
+    let refunds = shares_value(net_shares, nav, lp_supply);
+    remove_from_pool(storage, shares, refunds)?;
+    queue.exit_shares = net_shares;
+    queue.exit_refunds = refunds;
+
+    let lp_denom = format!("factory/{}/auction.{}", env.contract.address, lp_subdenom);
+    Ok(Some(config.token_factory_type.burn(
+        env.contract.address.clone(),
+        lp_denom.as_str(),
+        shares,
+    )))
+}
+
+/// Takes the share of the commitments out of the surplus and the basket once an auction is won.
+/// Their escrowed LP tokens are burned so the treasure chest only pays out the other LP holders,
+/// while their share of the surplus re-enters the pool with the new LP subdenom. Both shares are
+/// handed out as the settlement queue is processed. Returns the burn message, if any
+#[allow(clippy::too_many_arguments)]
+fn renew_commitments(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    lp_subdenom: u64,
+    lp_supply: Uint128,
+    surplus: &mut Uint128,
+    basket: &mut [Coin],
+    queue: &mut SettlementQueue,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    let committed = TOTAL_COMMITTED.may_load(storage)?.unwrap_or_default();
+    if committed.is_zero() {
+        return Ok(None);
+    }
+
+    // the rounding dust stays with the treasure chest unless every LP token is committed
+    let reentered = surplus.multiply_ratio(committed, lp_supply);
+    *surplus = surplus.checked_sub(reentered)?;
+    for coin in basket.iter_mut() {
+        let share = coin.amount.multiply_ratio(committed, lp_supply);
+        if !share.is_zero() {
+            coin.amount = coin.amount.checked_sub(share)?;
+            add_coin_to_basket(
+                &mut queue.commitment_rewards,
+                Coin {
+                    denom: coin.denom.clone(),
+                    amount: share,
+                },
+            )?;
+        }
+    }
+    TOTAL_COMMITTED.save(storage, &reentered)?;
+    queue.committed = committed;
+    queue.reentered = reentered;
+
+    let lp_denom = format!("factory/{}/auction.{}", env.contract.address, lp_subdenom);
+    Ok(Some(config.token_factory_type.burn(
+        env.contract.address.clone(),
+        lp_denom.as_str(),
+        committed,
+    )))
+}
+
+/// Adds the deposits pending for the auction round started, or any earlier one, to the pool,
+/// minting the LP tokens of its subdenom. The LP tokens are handed out as the settlement queue is
+/// processed. Returns the mint message, if any
+fn activate_pending_deposits(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    queue: &mut SettlementQueue,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    let amount = PENDING_DEPOSIT_TOTALS
+        .range(storage, None, Some(Bound::inclusive(queue.new_auction_round)), Order::Ascending)
+        .try_fold(Uint128::zero(), |total, item| -> Result<_, ContractError> {
+            Ok(total.checked_add(item?.1)?)
+        })?;
+    if amount.is_zero() {
+        return Ok(None);
+    }
+
+    let shares = add_to_pool(storage, amount)?;
+    queue.deposits = amount;
+    queue.deposit_shares = shares;
+
+    let lp_denom = format!("factory/{}/auction.{}", env.contract.address, queue.lp_subdenom);
+    Ok(Some(config.token_factory_type.mint(env.contract.address.clone(), &lp_denom, shares)))
+}
+
+/// Processes up to `limit` entries of the settlement queue, the pending exits first, then the
+/// commitments and the pending deposits. The queue is removed once every entry is processed
+pub(crate) fn process_settlement_queue(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    limit: usize,
+) -> Result<(Vec<CosmosMsg>, Vec<Attribute>), ContractError> {
+    let Some(mut queue) = SETTLEMENT_QUEUE.may_load(storage)? else {
+        return Ok((vec![], vec![]));
+    };
+
+    let mut messages = vec![];
+    let exits = process_pending_exits(storage, env, config, &mut queue, limit, &mut messages)?;
+    let (commitments, expired) =
+        process_commitments(storage, env, &mut queue, limit - exits, &mut messages)?;
+    let deposits = process_pending_deposits(
+        storage,
+        env,
+        &mut queue,
+        limit - exits - commitments,
+        &mut messages,
+    )?;
+
+    let mut attributes = vec![];
+    if expired > 0 {
+        attributes.push(attr("commitments_expired", expired.to_string()));
+    }
+    if exits > 0 {
+        attributes.push(attr("pending_exits_processed", exits.to_string()));
+    }
+    if deposits > 0 {
+        attributes.push(attr("pending_deposits_activated", deposits.to_string()));
+    }
+
+    let commitments_left = if queue.won {
+        !queue.committed.is_zero()
+    } else {
+        COMMITMENT_EXPIRIES
+            .prefix_range(
+                storage,
+                None,
+                Some(PrefixBound::inclusive(queue.auction_round)),
+                Order::Ascending,
+            )
+            .next()
+            .is_some()
+    };
+    let deposits_left = ROUND_PENDING_DEPOSITS
+        .prefix_range(
+            storage,
+            None,
+            Some(PrefixBound::inclusive(queue.new_auction_round)),
+            Order::Ascending,
+        )
+        .next()
+        .is_some();
+    if !PENDING_EXITS.is_empty(storage) || commitments_left || deposits_left {
+        SETTLEMENT_QUEUE.save(storage, &queue)?;
+        attributes.push(attr("settlement_queue_pending", queue.auction_round.to_string()));
+    } else {
+        SETTLEMENT_QUEUE.remove(storage);
+    }
+
+    Ok((messages, attributes))
+}
+
+/// Processes the exits requested while the pool was locked. If the auction was won, the escrowed
+/// LP tokens are handed back to be redeemed from the treasure chest, otherwise the native denom
+/// is refunded. Returns the number of exits processed
+fn process_pending_exits(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    queue: &mut SettlementQueue,
+    limit: usize,
+    messages: &mut Vec<CosmosMsg>,
+) -> Result<usize, ContractError> {
+    let pending_exits = PENDING_EXITS
+        .range(storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (address, pending_exit) in pending_exits.iter() {
+        PENDING_EXITS.remove(storage, address);
+
+        if queue.won {
+            let lp_denom =
+                format!("factory/{}/auction.{}", env.contract.address, pending_exit.lp_subdenom);
+            messages.push(
+                BankMsg::Send {
+                    to_address: address.to_string(),
+                    amount: coins(pending_exit.amount.u128(), lp_denom),
+                }
+                .into(),
+            );
+            continue;
+        }
+
+        // the refunds are taken out of what is left, so the last exit gets the rounding dust
+        let net_shares = pending_exit.amount.checked_sub(pending_exit.fee_shares)?;
+        if net_shares.is_zero() {
+            continue;
+        }
+        let refund = queue.exit_refunds.multiply_ratio(net_shares, queue.exit_shares);
+        queue.exit_shares = queue.exit_shares.checked_sub(net_shares)?;
+        queue.exit_refunds = queue.exit_refunds.checked_sub(refund)?;
+
+        // the exit fee is withdrawn from the deposits as well
+        let withdrawn = refund.multiply_ratio(pending_exit.amount, net_shares);
+        release_deposits(storage, address, pending_exit.lp_subdenom, withdrawn)?;
+
+        if !refund.is_zero() {
+            messages.push(
+                BankMsg::Send {
+                    to_address: address.to_string(),
+                    amount: coins(refund.u128(), config.native_denom.clone()),
+                }
+                .into(),
+            );
+        }
+    }
+
+    Ok(pending_exits.len())
+}
+
+/// Hands out the shares of the commitments renewed with the auction round won, releasing the new
+/// LP tokens of the ones that expired. If the auction was lost, the escrowed LP tokens of the
+/// commitments ending with it are released. Returns the number of commitments processed and
+/// expired
+fn process_commitments(
+    storage: &mut dyn Storage,
+    env: &Env,
+    queue: &mut SettlementQueue,
+    limit: usize,
+    messages: &mut Vec<CosmosMsg>,
+) -> Result<(usize, usize), ContractError> {
+    if !queue.won {
+        let expired = COMMITMENT_EXPIRIES
+            .prefix_range(
+                storage,
+                None,
+                Some(PrefixBound::inclusive(queue.auction_round)),
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(key, _)| key))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for (last_auction_round, address) in expired.iter() {
+            let commitment = COMMITMENTS.load(storage, address)?;
+            remove_commitment(storage, address, *last_auction_round, commitment.amount)?;
+
+            let lp_denom =
+                format!("factory/{}/auction.{}", env.contract.address, commitment.lp_subdenom);
+            messages.push(
+                BankMsg::Send {
+                    to_address: address.to_string(),
+                    amount: coins(commitment.amount.u128(), lp_denom),
+                }
+                .into(),
+            );
+        }
+
+        return Ok((expired.len(), expired.len()));
+    }
+
+    let commitments = COMMITMENTS
+        .range(
+            storage,
+            queue.commitments_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let lp_denom = format!("factory/{}/auction.{}", env.contract.address, queue.lp_subdenom);
+    let mut expired = 0;
+    for (address, mut commitment) in commitments.iter().cloned() {
+        // the shares are taken out of what is left, so the last commitment gets the rounding dust
+        let reentry = queue.reentered.multiply_ratio(commitment.amount, queue.committed);
+        queue.reentered = queue.reentered.checked_sub(reentry)?;
+
+        let mut rewards = vec![];
+        for coin in queue.commitment_rewards.iter_mut() {
+            let share = coin.amount.multiply_ratio(commitment.amount, queue.committed);
+            if !share.is_zero() {
+                coin.amount = coin.amount.checked_sub(share)?;
+                rewards.push(Coin {
+                    denom: coin.denom.clone(),
+                    amount: share,
+                });
+            }
+        }
+        queue.committed = queue.committed.checked_sub(commitment.amount)?;
+        queue.commitments_after = Some(address.clone());
+        if !rewards.is_empty() {
+            messages.push(
+                BankMsg::Send {
+                    to_address: address.to_string(),
+                    amount: rewards,
+                }
+                .into(),
+            );
+        }
+
+        if reentry.is_zero() {
+            remove_commitment(storage, &address, commitment.last_auction_round, reentry)?;
+            continue;
+        }
+
+        // the new round starts at a share price of 1
+        record_deposit(storage, &address, queue.new_auction_round, queue.lp_subdenom, reentry)?;
+
+        // the commitments ending with the auction round won get their new LP tokens
+        if commitment.last_auction_round <= queue.auction_round {
+            remove_commitment(storage, &address, commitment.last_auction_round, reentry)?;
+            messages.push(
+                BankMsg::Send {
+                    to_address: address.to_string(),
+                    amount: coins(reentry.u128(), lp_denom.as_str()),
+                }
+                .into(),
+            );
+            expired += 1;
+            continue;
+        }
+
+        commitment.lp_subdenom = queue.lp_subdenom;
+        commitment.amount = reentry;
+        COMMITMENTS.save(storage, &address, &commitment)?;
+    }
+    queue.commitment_rewards.retain(|coin| !coin.amount.is_zero());
+
+    Ok((commitments.len(), expired))
+}
+
+/// Removes the commitment of the address, releasing the given amount of escrowed LP tokens
+fn remove_commitment(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    last_auction_round: u64,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    COMMITMENTS.remove(storage, address);
+    COMMITMENT_EXPIRIES.remove(storage, (last_auction_round, address));
+    let total = TOTAL_COMMITTED.may_load(storage)?.unwrap_or_default().checked_sub(amount)?;
+    TOTAL_COMMITTED.save(storage, &total)?;
+    Ok(())
+}
+
+/// Hands out the LP tokens of the deposits activated when the auction round started. Returns the
+/// number of deposits processed
+fn process_pending_deposits(
+    storage: &mut dyn Storage,
+    env: &Env,
+    queue: &mut SettlementQueue,
+    limit: usize,
+    messages: &mut Vec<CosmosMsg>,
+) -> Result<usize, ContractError> {
+    let pending_deposits = ROUND_PENDING_DEPOSITS
+        .prefix_range(
+            storage,
+            None,
+            Some(PrefixBound::inclusive(queue.new_auction_round)),
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let lp_denom = format!("factory/{}/auction.{}", env.contract.address, queue.lp_subdenom);
+    for ((pending_auction_round, address), amount) in pending_deposits.iter() {
+        remove_pending_deposit(storage, address, *pending_auction_round)?;
+
+        // the shares are taken out of what is left, so the last deposit gets the rounding dust
+        let shares = queue.deposit_shares.multiply_ratio(*amount, queue.deposits);
+        queue.deposit_shares = queue.deposit_shares.checked_sub(shares)?;
+        queue.deposits = queue.deposits.checked_sub(*amount)?;
+        record_deposit(storage, address, queue.new_auction_round, queue.lp_subdenom, *amount)?;
+
+        if !shares.is_zero() {
+            messages.push(
+                BankMsg::Send {
+                    to_address: address.to_string(),
+                    amount: coins(shares.u128(), lp_denom.as_str()),
+                }
+                .into(),
+            );
+        }
+    }
+
+    Ok(pending_deposits.len())
+}
+
+/// Returns the net asset value of the pool in native denom and the supply of the current LP denom.
+/// LP tokens used to be minted 1:1 with the native denom, so the supply defaults to the NAV
+pub(crate) fn pool_nav(storage: &dyn Storage) -> StdResult<(Uint128, Uint128)> {
+    let nav = BIDDING_BALANCE.load(storage)?;
+    let lp_supply = LP_SUPPLY.may_load(storage)?.unwrap_or(nav);
+    Ok((nav, lp_supply))
+}
+
+/// Returns the value in native denom of the given amount of LP tokens
+pub(crate) fn shares_value(shares: Uint128, nav: Uint128, lp_supply: Uint128) -> Uint128 {
+    if lp_supply.is_zero() {
+        return Uint128::zero();
+    }
+    shares.multiply_ratio(nav, lp_supply)
+}
+
+/// Adds the amount of native denom to the pool, returning the LP tokens to mint for it at the
+/// current share price
+pub(crate) fn add_to_pool(
+    storage: &mut dyn Storage,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let (nav, lp_supply) = pool_nav(storage)?;
+    let shares = if nav.is_zero() || lp_supply.is_zero() {
+        amount
+    } else {
+        amount.multiply_ratio(lp_supply, nav)
+    };
+
+    BIDDING_BALANCE.save(storage, &nav.checked_add(amount)?)?;
+    LP_SUPPLY.save(storage, &lp_supply.checked_add(shares)?)?;
+    Ok(shares)
+}
+
+/// Removes the LP tokens burned and the amount of native denom sent out from the pool
+pub(crate) fn remove_from_pool(
+    storage: &mut dyn Storage,
+    shares: Uint128,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let (nav, lp_supply) = pool_nav(storage)?;
+    BIDDING_BALANCE.save(storage, &nav.checked_sub(amount)?)?;
+    LP_SUPPLY.save(storage, &lp_supply.checked_sub(shares)?)?;
+    Ok(())
+}
+
+/// Adds a deposit that joins the pool on the given auction round
+pub(crate) fn add_pending_deposit(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    auction_round: u64,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let total = PENDING_DEPOSITS.update::<_, ContractError>(
+        storage,
+        (address, auction_round),
+        |pending| Ok(pending.unwrap_or_default().checked_add(amount)?),
+    )?;
+    ROUND_PENDING_DEPOSITS.save(storage, (auction_round, address), &total)?;
+    PENDING_DEPOSIT_TOTALS.update::<_, ContractError>(storage, auction_round, |pending| {
+        Ok(pending.unwrap_or_default().checked_add(amount)?)
+    })?;
+    let total =
+        TOTAL_PENDING_DEPOSITS.may_load(storage)?.unwrap_or_default().checked_add(amount)?;
+    TOTAL_PENDING_DEPOSITS.save(storage, &total)?;
+    Ok(total)
+}
+
+/// Removes the deposit of the address pending for the given auction round, returning its amount
+pub(crate) fn remove_pending_deposit(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    auction_round: u64,
+) -> Result<Uint128, ContractError> {
+    let amount = PENDING_DEPOSITS.may_load(storage, (address, auction_round))?.ok_or(
+        ContractError::NoPendingDeposit {
+            address: address.to_string(),
+            auction_round,
+        },
+    )?;
+    PENDING_DEPOSITS.remove(storage, (address, auction_round));
+    ROUND_PENDING_DEPOSITS.remove(storage, (auction_round, address));
+
+    let total = PENDING_DEPOSIT_TOTALS
+        .may_load(storage, auction_round)?
+        .unwrap_or_default()
+        .checked_sub(amount)?;
+    if total.is_zero() {
+        PENDING_DEPOSIT_TOTALS.remove(storage, auction_round);
+    } else {
+        PENDING_DEPOSIT_TOTALS.save(storage, auction_round, &total)?;
+    }
+    let total =
+        TOTAL_PENDING_DEPOSITS.may_load(storage)?.unwrap_or_default().checked_sub(amount)?;
+    TOTAL_PENDING_DEPOSITS.save(storage, &total)?;
+    Ok(amount)
+}
+
+/// Returns whether the address holds the role. The owner holds every admin role, and the
+/// whitelisted addresses hold the bidder and settler roles
+pub(crate) fn has_role(
+    storage: &dyn Storage,
+    env: &Env,
+    address: &Addr,
+    role: &Role,
+) -> StdResult<bool> {
+    let implicit = if role.is_admin() {
+        cw_ownable::is_owner(storage, address)?
+    } else {
+        WHITELISTED_ADDRESSES.has(storage, address)
+    };
+    if implicit {
+        return Ok(true);
+    }
+
+    let now = env.block.time.seconds();
+    Ok(ROLES
+        .may_load(storage, (role.to_string().as_str(), address))?
+        .is_some_and(|grant| !grant.expires_at.is_some_and(|expires_at| expires_at <= now)))
+}
+
+/// Fails if the address does not hold the role
+pub(crate) fn assert_role(
+    storage: &dyn Storage,
+    env: &Env,
+    address: &Addr,
+    role: &Role,
+) -> Result<(), ContractError> {
+    if !has_role(storage, env, address, role)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Fails if the given operation is paused
+pub(crate) fn assert_not_paused(
+    storage: &dyn Storage,
+    operation: &str,
+    is_paused: fn(&PauseStatus) -> bool,
+) -> Result<(), ContractError> {
+    if is_paused(&PAUSE_STATUS.may_load(storage)?.unwrap_or_default()) {
+        return Err(ContractError::Paused {
+            operation: operation.to_string(),
+        });
+    }
+    Ok(())
+}
+
 /// Validates the rewards fee
 pub(crate) fn validate_percentage(percentage: Decimal) -> Result<Decimal, ContractError> {
     if percentage > Decimal::percent(100) {
@@ -292,6 +1169,92 @@ pub(crate) fn validate_percentage(percentage: Decimal) -> Result<Decimal, Contra
     Ok(percentage)
 }
 
+/// Validates the exit fee schedule
+pub(crate) fn validate_exit_fee_schedule(
+    schedule: ExitFeeSchedule,
+) -> Result<ExitFeeSchedule, ContractError> {
+    validate_percentage(schedule.base_fee)?;
+    validate_percentage(schedule.max_fee)?;
+    if schedule.max_fee < schedule.base_fee {
+        return Err(ContractError::InvalidExitFeeSchedule {
+            reason: "max fee must not be lower than the base fee".to_string(),
+        });
+    }
+    Ok(schedule)
+}
+
+/// Returns the fee rate charged on exits at the current time, growing linearly from the base fee
+/// to the max fee during the `ramp_seconds` before the auction closing time
+pub(crate) fn exit_fee_rate(
+    config: &Config,
+    env: &Env,
+    closing_time: u64,
+) -> Result<Decimal, ContractError> {
+    let schedule = &config.exit_fee;
+    let now = env.block.time.seconds();
+    if now >= closing_time {
+        return Ok(schedule.max_fee);
+    }
+
+    let ramp_starts_at = closing_time.saturating_sub(schedule.ramp_seconds);
+    if schedule.ramp_seconds == 0 || now < ramp_starts_at {
+        return Ok(schedule.base_fee);
+    }
+
+    let elapsed = Decimal::from_ratio(now - ramp_starts_at, schedule.ramp_seconds);
+    Ok(schedule
+        .base_fee
+        .checked_add(schedule.max_fee.checked_sub(schedule.base_fee)?.checked_mul(elapsed)?)?)
+}
+
+/// Validates the bid strategy
+pub(crate) fn validate_bid_strategy(strategy: BidStrategy) -> Result<BidStrategy, ContractError> {
+    match &strategy {
+        BidStrategy::JumpBid {
+            fraction,
+        } => {
+            validate_percentage(*fraction)?;
+        },
+        BidStrategy::Ladder {
+            step,
+        } if step.is_zero() => {
+            return Err(ContractError::InvalidBidStrategy {
+                reason: "ladder step must be greater than zero".to_string(),
+            });
+        },
+        _ => {},
+    }
+    Ok(strategy)
+}
+
+/// Computes the amount to bid with the given strategy. The amount is always between the minimum
+/// and maximum allowed bids, with the minimum allowed bid expected to be the lowest of both
+pub(crate) fn compute_bid_amount(
+    strategy: &BidStrategy,
+    minimum_allowed_bid: Uint128,
+    maximum_allowed_bid: Uint128,
+) -> Result<Uint128, ContractError> {
+    let bid_amount = match strategy {
+        BidStrategy::MinimalIncrement => minimum_allowed_bid,
+        BidStrategy::JumpBid {
+            fraction,
+        } => maximum_allowed_bid.checked_mul_floor(*fraction)?,
+        BidStrategy::Ladder {
+            step,
+        } => {
+            let remainder = minimum_allowed_bid.checked_rem(*step)?;
+            if remainder.is_zero() {
+                minimum_allowed_bid
+            } else {
+                minimum_allowed_bid.checked_add(step.checked_sub(remainder)?)?
+            }
+        },
+        BidStrategy::MaxAllowed => maximum_allowed_bid,
+    };
+
+    Ok(bid_amount.clamp(minimum_allowed_bid, maximum_allowed_bid))
+}
+
 /// Queries the current auction
 pub(crate) fn query_current_auction(
     deps: Deps,
@@ -305,6 +1268,64 @@ pub(crate) fn query_current_auction(
     Ok(current_auction_basket_response)
 }
 
+/// Returns when users cannot exit the pool. The pool is locked from `lock_period_seconds` before
+/// the unsettled auction closes, and from the contract's bid until the current auction closes.
+pub(crate) fn lock_status(deps: Deps, env: &Env) -> StdResult<LockStatusResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let closing_time = UNSETTLED_AUCTION.load(deps.storage)?.closing_time;
+    let now = env.block.time.seconds();
+
+    // the contract's bid is locked until the current auction closes, even if it already moved to a
+    // new round while the previous one is not settled yet
+    let bid_lock_ends_at = if FUNDS_LOCKED.load(deps.storage)? {
+        Some(query_current_auction(deps)?.auction_closing_time.i64() as u64)
+    } else {
+        None
+    };
+    let bid_locked = bid_lock_ends_at.is_some_and(|ends_at| now < ends_at);
+
+    // while bidding is paused the contract cannot bid before the auction closes, so users can still
+    // leave the pool
+    let bid_paused = PAUSE_STATUS.may_load(deps.storage)?.unwrap_or_default().bid;
+    let lock_starts_at = closing_time.saturating_sub(config.lock_period_seconds);
+    let time_locked = !bid_paused && lock_starts_at <= now && now < closing_time;
+    let lock_ends_at = match bid_lock_ends_at {
+        Some(ends_at) if bid_locked => ends_at,
+        _ => closing_time,
+    };
+
+    Ok(LockStatusResponse {
+        lock_period_seconds: config.lock_period_seconds,
+        lock_starts_at,
+        lock_ends_at,
+        bid_locked,
+        time_locked,
+        is_locked: bid_locked || time_locked,
+    })
+}
+
+/// Returns the part of the bidding balance exceeding the outstanding bid of the contract and the
+/// buffer kept for re-bids, which can be withdrawn while the pool is locked
+pub(crate) fn withdrawable_surplus(deps: Deps) -> StdResult<Uint128> {
+    let config = CONFIG.load(deps.storage)?;
+    let auction_round = UNSETTLED_AUCTION.load(deps.storage)?.auction_round;
+
+    // bids are increasing, so the last one of each round is the outstanding bid of the contract.
+    // When the settlement lags behind, the contract may have bid on later rounds as well
+    let outstanding_bid = ROUND_BIDS
+        .range(deps.storage, Some(Bound::inclusive(auction_round)), None, Order::Ascending)
+        .try_fold(Uint128::zero(), |total, item| -> StdResult<_> {
+            let (_, bids) = item?;
+            Ok(total.checked_add(bids.last().map_or(Uint128::zero(), |bid| bid.amount))?)
+        })?;
+    let buffer = outstanding_bid
+        .checked_mul_ceil(config.rebid_buffer)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let reserved = outstanding_bid.checked_add(buffer)?;
+
+    Ok(BIDDING_BALANCE.load(deps.storage)?.saturating_sub(reserved))
+}
+
 // Adds coins to the basket or increments the amount if the coin already exists (avoiding duplicates)
 fn add_coin_to_basket(basket: &mut Vec<Coin>, coin: Coin) -> Result<(), ContractError> {
     if let Some(existing_coin) = basket.iter_mut().find(|c| c.denom == coin.denom) {
@@ -315,6 +1336,155 @@ fn add_coin_to_basket(basket: &mut Vec<Coin>, coin: Coin) -> Result<(), Contract
     Ok(())
 }
 
+/// Adds the amount to the deposits of the address for the given auction round
+pub(crate) fn record_deposit(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    auction_round: u64,
+    lp_subdenom: u64,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let total = DEPOSITOR_TOTALS.may_load(storage, (address, lp_subdenom))?.unwrap_or_default();
+    if total.is_zero() {
+        prune_deposits(storage, address, lp_subdenom)?;
+
+        let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+        stats.depositor_count += 1;
+        STATS.save(storage, &stats)?;
+    }
+    DEPOSITOR_TOTALS.save(storage, (address, lp_subdenom), &total.checked_add(amount)?)?;
+
+    let deposit =
+        DEPOSITS.update::<_, ContractError>(storage, (address, auction_round), |deposit| {
+            let mut deposit = deposit.unwrap_or(Deposit {
+                lp_subdenom,
+                amount: Uint128::zero(),
+            });
+            deposit.amount = deposit.amount.checked_add(amount)?;
+            Ok(deposit)
+        })?;
+
+    ROUND_DEPOSITORS.save(storage, (auction_round, address), &deposit.amount)?;
+    Ok(())
+}
+
+/// Removes the deposits of the address made with an older LP subdenom, which are redeemable from the
+/// treasure chest of the round won, so that the ledger only keeps the current deposits
+fn prune_deposits(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    lp_subdenom: u64,
+) -> Result<(), ContractError> {
+    let stale_deposits = DEPOSITS
+        .prefix(address)
+        .range(storage, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, deposit)) => deposit.lp_subdenom < lp_subdenom,
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (auction_round, deposit) in stale_deposits {
+        DEPOSITS.remove(storage, (address, auction_round));
+        ROUND_DEPOSITORS.remove(storage, (auction_round, address));
+        DEPOSITOR_TOTALS.remove(storage, (address, deposit.lp_subdenom));
+    }
+
+    Ok(())
+}
+
+/// Removes the amount from the deposits of the address made with the given LP subdenom, starting
+/// with the most recent auction round. As LP tokens can be transferred, the amount removed is
+/// capped to what the address deposited. Returns the amount removed from the ledger
+pub(crate) fn release_deposits(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    lp_subdenom: u64,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let deposits = DEPOSITS
+        .prefix(address)
+        .range(storage, None, None, Order::Descending)
+        .filter(|item| match item {
+            Ok((_, deposit)) => deposit.lp_subdenom == lp_subdenom,
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut remaining = amount;
+    for (auction_round, mut deposit) in deposits {
+        if remaining.is_zero() {
+            break;
+        }
+
+        let released = remaining.min(deposit.amount);
+        remaining = remaining.checked_sub(released)?;
+        deposit.amount = deposit.amount.checked_sub(released)?;
+
+        if deposit.amount.is_zero() {
+            DEPOSITS.remove(storage, (address, auction_round));
+            ROUND_DEPOSITORS.remove(storage, (auction_round, address));
+        } else {
+            DEPOSITS.save(storage, (address, auction_round), &deposit)?;
+            ROUND_DEPOSITORS.save(storage, (auction_round, address), &deposit.amount)?;
+        }
+    }
+
+    let released = amount.checked_sub(remaining)?;
+    if !released.is_zero() {
+        let total = DEPOSITOR_TOTALS
+            .may_load(storage, (address, lp_subdenom))?
+            .unwrap_or_default()
+            .saturating_sub(released);
+        if total.is_zero() {
+            DEPOSITOR_TOTALS.remove(storage, (address, lp_subdenom));
+            let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+            stats.depositor_count = stats.depositor_count.saturating_sub(1);
+            STATS.save(storage, &stats)?;
+        } else {
+            DEPOSITOR_TOTALS.save(storage, (address, lp_subdenom), &total)?;
+        }
+    }
+
+    Ok(released)
+}
+
+/// Checks a slashable keeper bond can't be claimed before the settlement it backs is final and the
+/// auction module can still be checked against it, that is for a challenge period and an auction
+/// period
+pub(crate) fn validate_keeper_unbonding_period(
+    deps: Deps,
+    config: &Config,
+) -> Result<(), ContractError> {
+    if config.keeper_min_bond.is_zero() {
+        return Ok(());
+    }
+
+    let min_unbonding_period =
+        config.settlement_challenge_period.saturating_add(query_auction_period(deps)?);
+    if config.keeper_unbonding_period < min_unbonding_period {
+        return Err(ContractError::KeeperUnbondingPeriodTooShort {
+            min_unbonding_period,
+        });
+    }
+
+    Ok(())
+}
+
+/// Queries the duration of an auction round from the auction module params
+pub(crate) fn query_auction_period(deps: Deps) -> StdResult<u64> {
+    let auction_params_response: QueryAuctionParamsResponse =
+        deps.querier.query(&QueryRequest::Stargate {
+            path: "/injective.auction.v1beta1.Query/AuctionParams".to_string(),
+            data: [].into(),
+        })?;
+    let params = auction_params_response
+        .params
+        .ok_or_else(|| StdError::generic_err("auction params not found"))?;
+
+    Ok(params.auction_period.max(0) as u64)
+}
+
 /// Queries the latest auction result
 pub(crate) fn query_latest_auction_result(deps: Deps) -> StdResult<QueryLastAuctionResultResponse> {
     let last_auction_result_response: QueryLastAuctionResultResponse =
@@ -2,6 +2,7 @@ pub mod contract;
 mod error;
 pub mod executions;
 pub mod helpers;
+pub mod pricing;
 pub mod queries;
 pub mod state;
 
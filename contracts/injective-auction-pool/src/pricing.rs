@@ -0,0 +1,309 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Api, Coin, Decimal, Deps, Env, Int64, QueryRequest, Uint128, Uint64};
+use injective_auction::auction_pool::{Config, PriceFeedConfig, PriceSource, PricingConfig};
+use injective_std::types::injective::{
+    exchange::v1beta1::{QuerySpotMidPriceAndTobRequest, QuerySpotMidPriceAndTobResponse},
+    oracle::v1beta1::{QueryOraclePriceRequest, QueryOraclePriceResponse, ScalingOptions},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{helpers::validate_percentage, ContractError};
+
+/// Query message of the price feed contract, following the Pyth interface
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceFeedQueryMsg {
+    PriceFeed {
+        id: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PriceFeedResponse {
+    pub price_feed: PriceFeed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PriceFeed {
+    pub id: String,
+    pub price: FeedPrice,
+}
+
+/// A USD price with a confidence interval, both in units of 10^expo
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FeedPrice {
+    pub price: Int64,
+    pub conf: Uint64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Values the basket in native denom using the configured price sources. The native denom is
+/// valued 1:1 and denoms without a price source are valued at zero
+pub(crate) fn query_basket_value(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    basket: &[Coin],
+) -> Result<Uint128, ContractError> {
+    let mut value = Uint128::zero();
+
+    for coin in basket.iter() {
+        if coin.denom == config.native_denom {
+            value = value.checked_add(coin.amount)?;
+            continue;
+        }
+
+        let Some(price_source) =
+            config.pricing.price_sources.iter().find(|source| source.denom == coin.denom)
+        else {
+            continue;
+        };
+
+        let price = query_price(deps, env, &config.pricing, &coin.denom, &price_source.source)?;
+        value = value.checked_add(coin.amount.checked_mul_floor(price)?)?;
+    }
+
+    Ok(value)
+}
+
+/// Returns the first denom of the basket valued with a spot market mid price. The mid price has
+/// no timestamp and can be moved within a block, so it is only trusted to cross-check a basket
+/// value supplied by a bidder
+pub(crate) fn spot_priced_denom(config: &Config, basket: &[Coin]) -> Option<String> {
+    basket
+        .iter()
+        .filter(|coin| coin.denom != config.native_denom)
+        .find(|coin| {
+            config.pricing.price_sources.iter().any(|source| {
+                source.denom == coin.denom
+                    && matches!(source.source, PriceSource::SpotMarket { .. })
+            })
+        })
+        .map(|coin| coin.denom.clone())
+}
+
+/// Queries the price of one unit of the denom in units of native denom
+fn query_price(
+    deps: Deps,
+    env: &Env,
+    pricing: &PricingConfig,
+    denom: &str,
+    source: &PriceSource,
+) -> Result<Decimal, ContractError> {
+    let price_unavailable = || ContractError::PriceUnavailable {
+        denom: denom.to_string(),
+    };
+
+    match source {
+        PriceSource::Oracle {
+            oracle_type,
+            base,
+            quote,
+            base_decimals,
+            quote_decimals,
+        } => {
+            let response: QueryOraclePriceResponse =
+                deps.querier.query(&QueryRequest::Stargate {
+                    path: "/injective.oracle.v1beta1.Query/OraclePrice".to_string(),
+                    data: QueryOraclePriceRequest {
+                        oracle_type: *oracle_type,
+                        base: base.clone(),
+                        quote: quote.clone(),
+                        scaling_options: Some(ScalingOptions {
+                            base_decimals: *base_decimals,
+                            quote_decimals: *quote_decimals,
+                        }),
+                    }
+                    .into(),
+                })?;
+
+            let price_pair_state = response.price_pair_state.ok_or_else(price_unavailable)?;
+
+            // the pair price is as old as the oldest of its prices
+            let updated_at =
+                price_pair_state.base_timestamp.min(price_pair_state.quote_timestamp).max(0) as u64;
+            if env.block.time.seconds().saturating_sub(updated_at) > pricing.max_price_age {
+                return Err(ContractError::StalePrice {
+                    denom: denom.to_string(),
+                    updated_at,
+                });
+            }
+
+            Decimal::from_str(&price_pair_state.pair_price).map_err(|_| price_unavailable())
+        },
+        PriceSource::SpotMarket {
+            market_id,
+        } => {
+            let response: QuerySpotMidPriceAndTobResponse =
+                deps.querier.query(&QueryRequest::Stargate {
+                    path: "/injective.exchange.v1beta1.Query/SpotMidPriceAndTOB".to_string(),
+                    data: QuerySpotMidPriceAndTobRequest {
+                        market_id: market_id.clone(),
+                    }
+                    .into(),
+                })?;
+
+            // the mid price is empty if one side of the order book is empty
+            Decimal::from_str(&response.mid_price).map_err(|_| price_unavailable())
+        },
+        PriceSource::PriceFeed {
+            feed_id,
+            decimals,
+        } => {
+            let invalid_price_feed = |reason: &str| ContractError::InvalidPriceFeed {
+                feed_id: feed_id.clone(),
+                reason: reason.to_string(),
+            };
+
+            let price_feed = pricing
+                .price_feed
+                .as_ref()
+                .ok_or_else(|| invalid_price_feed("no price feed contract configured"))?;
+
+            let price = query_feed_price(deps, env, pricing, price_feed, feed_id)?;
+            let native_price =
+                query_feed_price(deps, env, pricing, price_feed, &price_feed.native_feed_id)?;
+
+            // convert the USD prices to native denom, then scale to chain units
+            let price = price
+                .checked_div(native_price)
+                .map_err(|_| invalid_price_feed("price is out of range"))?;
+            let scale = |exponent: u32| {
+                Uint128::new(10)
+                    .checked_pow(exponent)
+                    .ok()
+                    .and_then(|scale| Decimal::checked_from_ratio(scale, 1u128).ok())
+                    .ok_or_else(|| invalid_price_feed("decimals are out of range"))
+            };
+            if price_feed.native_decimals >= *decimals {
+                price
+                    .checked_mul(scale(price_feed.native_decimals - decimals)?)
+                    .map_err(|_| invalid_price_feed("price is out of range"))
+            } else {
+                price
+                    .checked_div(scale(decimals - price_feed.native_decimals)?)
+                    .map_err(|_| invalid_price_feed("price is out of range"))
+            }
+        },
+    }
+}
+
+/// Queries the USD price of a feed from the price feed contract, checking its age and confidence
+/// interval
+fn query_feed_price(
+    deps: Deps,
+    env: &Env,
+    pricing: &PricingConfig,
+    price_feed: &PriceFeedConfig,
+    feed_id: &str,
+) -> Result<Decimal, ContractError> {
+    let invalid_price_feed = |reason: String| ContractError::InvalidPriceFeed {
+        feed_id: feed_id.to_string(),
+        reason,
+    };
+
+    let response: PriceFeedResponse = deps
+        .querier
+        .query_wasm_smart(
+            &price_feed.contract,
+            &PriceFeedQueryMsg::PriceFeed {
+                id: feed_id.to_string(),
+            },
+        )
+        .map_err(|err| invalid_price_feed(err.to_string()))?;
+
+    let FeedPrice {
+        price,
+        conf,
+        expo,
+        publish_time,
+    } = response.price_feed.price;
+
+    if env.block.time.seconds().saturating_sub(publish_time.max(0) as u64) > pricing.max_price_age {
+        return Err(invalid_price_feed(format!("price is stale, published at {publish_time}")));
+    }
+
+    if price.i64() <= 0 {
+        return Err(invalid_price_feed("price is not positive".to_string()));
+    }
+    let price = price.i64() as u128;
+
+    if Decimal::from_ratio(conf.u64(), price) > price_feed.max_confidence {
+        return Err(invalid_price_feed("confidence interval is too wide".to_string()));
+    }
+
+    let price = if expo <= 0 {
+        Decimal::from_atomics(price, expo.unsigned_abs()).ok()
+    } else {
+        10u128
+            .checked_pow(expo as u32)
+            .and_then(|scale| price.checked_mul(scale))
+            .and_then(|price| Decimal::from_atomics(price, 0).ok())
+    };
+
+    price.ok_or_else(|| invalid_price_feed("price is out of range".to_string()))
+}
+
+/// Checks that the basket value sent by the caller is within the allowed tolerance of the on-chain
+/// valuation
+pub(crate) fn check_basket_value(
+    pricing: &PricingConfig,
+    supplied: Uint128,
+    on_chain: Uint128,
+) -> Result<(), ContractError> {
+    let difference = supplied.abs_diff(on_chain);
+    if difference > on_chain.mul_floor(pricing.basket_value_tolerance) {
+        return Err(ContractError::BasketValueMismatch {
+            supplied,
+            on_chain,
+        });
+    }
+    Ok(())
+}
+
+/// Validates the pricing settings
+pub(crate) fn validate_pricing(
+    api: &dyn Api,
+    pricing: PricingConfig,
+) -> Result<PricingConfig, ContractError> {
+    validate_percentage(pricing.basket_value_tolerance)?;
+
+    if let Some(price_feed) = &pricing.price_feed {
+        api.addr_validate(price_feed.contract.as_str())?;
+        validate_percentage(price_feed.max_confidence)?;
+    }
+
+    for (i, price_source) in pricing.price_sources.iter().enumerate() {
+        if pricing.price_sources[..i].iter().any(|other| other.denom == price_source.denom) {
+            return Err(ContractError::DuplicatePriceSource {
+                denom: price_source.denom.clone(),
+            });
+        }
+
+        if let PriceSource::PriceFeed {
+            feed_id,
+            ..
+        } = &price_source.source
+        {
+            if pricing.price_feed.is_none() {
+                return Err(ContractError::InvalidPriceFeed {
+                    feed_id: feed_id.clone(),
+                    reason: "no price feed contract configured".to_string(),
+                });
+            }
+        }
+
+        // oracle and price feed prices are used for permissionless bids, so they must be fresh
+        if pricing.max_price_age == 0
+            && !matches!(price_source.source, PriceSource::SpotMarket { .. })
+        {
+            return Err(ContractError::MissingMaxPriceAge {
+                denom: price_source.denom.clone(),
+            });
+        }
+    }
+
+    Ok(pricing)
+}
@@ -1,11 +1,24 @@
-use crate::helpers::query_current_auction;
+use crate::helpers::{
+    exit_fee_rate, lock_status, pool_nav, query_current_auction, shares_value, withdrawable_surplus,
+};
 use crate::state::{
-    BIDDING_BALANCE, CONFIG, TREASURE_CHEST_CONTRACTS, UNSETTLED_AUCTION, WHITELISTED_ADDRESSES,
+    ATTESTATION_REQUIRED, BIDDING_BALANCE, COMMITMENTS, CONFIG, CONFIG_PROPOSAL, DEPOSITS,
+    KEEPER_BONDS, KEEPER_REPORTS, PAUSE_STATUS, PENDING_DEPOSITS, PENDING_DEPOSIT_TOTALS,
+    PENDING_EXITS, PENDING_SETTLEMENT, ROLES, ROUND_DEPOSITORS, ROUND_HISTORY, SETTLEMENT_QUEUE,
+    SETTLEMENT_REPORTS, SLASHINGS, STATS, TREASURE_CHEST_CONTRACTS, UNSETTLED_AUCTION,
+    WHITELISTED_ADDRESSES,
+};
+use cosmwasm_std::{
+    to_json_binary, Binary, Decimal, Deps, Env, Order, StdError, StdResult, Uint128,
 };
-use cosmwasm_std::{to_json_binary, Binary, Deps, StdResult};
+use cw_storage_plus::Bound;
 use injective_auction::auction_pool::{
-    BiddingBalanceResponse, ConfigResponse, TreasureChestContractsResponse,
-    WhitelistedAddressesResponse,
+    BidWindowResponse, BiddingBalanceResponse, CommitmentsResponse, ConfigResponse,
+    DepositorsResponse, KeeperBondsResponse, KeeperReportsResponse, NavResponse, PendingDeposit,
+    PendingDepositTotalsResponse, PendingDepositsResponse, PendingExitsResponse, Role, RoleHolder,
+    RoleHoldersResponse, RoundDeposit, RoundHistoryResponse, SlashingsResponse, StatsResponse,
+    TreasureChestContractsResponse, UserPositionResponse, WhitelistedAddressesResponse,
+    WithdrawableNowResponse,
 };
 
 pub fn query_config(deps: Deps) -> StdResult<Binary> {
@@ -14,6 +27,10 @@ pub fn query_config(deps: Deps) -> StdResult<Binary> {
     })
 }
 
+pub fn query_config_proposal(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&CONFIG_PROPOSAL.may_load(deps.storage)?)
+}
+
 pub fn query_whitelisted_addresses(deps: Deps) -> StdResult<Binary> {
     let whitelisted_addresses: StdResult<Vec<String>> = WHITELISTED_ADDRESSES
         .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
@@ -61,3 +78,345 @@ pub fn query_unsettled_auction(deps: Deps) -> StdResult<Binary> {
 
     to_json_binary(&unsettled_auction)
 }
+
+pub fn query_user_position(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let lp_subdenom = UNSETTLED_AUCTION.load(deps.storage)?.lp_subdenom;
+
+    let deposits = DEPOSITS
+        .prefix(&address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(auction_round, deposit)| RoundDeposit {
+                auction_round,
+                lp_subdenom: deposit.lp_subdenom,
+                amount: deposit.amount,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let active_amount = deposits
+        .iter()
+        .filter(|deposit| deposit.lp_subdenom == lp_subdenom)
+        .map(|deposit| deposit.amount)
+        .sum::<Uint128>();
+
+    to_json_binary(&UserPositionResponse {
+        deposits,
+        active_amount,
+    })
+}
+
+pub fn query_depositors(
+    deps: Deps,
+    round: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+
+    let depositors = ROUND_DEPOSITORS
+        .prefix(round)
+        .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit.map_or(usize::MAX, |limit| limit as usize))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&DepositorsResponse {
+        depositors,
+    })
+}
+
+pub fn query_round_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let rounds = cw_paginate_storage::paginate_map(
+        deps,
+        &ROUND_HISTORY,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?
+    .into_iter()
+    .map(|(_, record)| record)
+    .collect();
+
+    to_json_binary(&RoundHistoryResponse {
+        rounds,
+    })
+}
+
+pub fn query_round(deps: Deps, round: u64) -> StdResult<Binary> {
+    let record = ROUND_HISTORY.load(deps.storage, round)?;
+
+    to_json_binary(&record)
+}
+
+pub fn query_stats(deps: Deps) -> StdResult<Binary> {
+    let stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+
+    to_json_binary(&StatsResponse {
+        stats,
+    })
+}
+
+pub fn query_bid_window(deps: Deps, env: Env) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let closes_at = query_current_auction(deps)?.auction_closing_time.i64() as u64;
+    let opens_at = if config.bid_window > 0 {
+        closes_at.saturating_sub(config.bid_window)
+    } else {
+        0
+    };
+    let now = env.block.time.seconds();
+
+    to_json_binary(&BidWindowResponse {
+        bid_window: config.bid_window,
+        opens_at,
+        closes_at,
+        is_open: opens_at <= now && now < closes_at,
+    })
+}
+
+pub fn query_lock_status(deps: Deps, env: Env) -> StdResult<Binary> {
+    to_json_binary(&lock_status(deps, &env)?)
+}
+
+pub fn query_pending_deposits(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+
+    let deposits = PENDING_DEPOSITS
+        .prefix(&address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(auction_round, amount)| PendingDeposit {
+                auction_round,
+                amount,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&PendingDepositsResponse {
+        deposits,
+    })
+}
+
+pub fn query_pending_deposit_totals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let totals = cw_paginate_storage::paginate_map(
+        deps,
+        &PENDING_DEPOSIT_TOTALS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?;
+
+    to_json_binary(&PendingDepositTotalsResponse {
+        totals,
+    })
+}
+
+pub fn query_withdrawable_now(deps: Deps, env: Env, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+    let lp_subdenom = UNSETTLED_AUCTION.load(deps.storage)?.lp_subdenom;
+    let lp_denom = format!("factory/{}/auction.{}", env.contract.address, lp_subdenom);
+    let balance = deps.querier.query_balance(&address, lp_denom)?.amount;
+
+    let pause_status = PAUSE_STATUS.may_load(deps.storage)?.unwrap_or_default();
+    let lock_status = lock_status(deps, &env)?;
+    let (nav, lp_supply) = pool_nav(deps.storage)?;
+    let shares = if pause_status.exit || lock_status.time_locked || nav.is_zero() {
+        Uint128::zero()
+    } else if lock_status.bid_locked {
+        balance.multiply_ratio(withdrawable_surplus(deps)?, nav)
+    } else {
+        balance
+    };
+
+    // the exit fee is kept in the bidding balance, as when exiting the pool
+    let amount = shares_value(shares, nav, lp_supply);
+    let closing_time = UNSETTLED_AUCTION.load(deps.storage)?.closing_time;
+    let exit_fee_rate = exit_fee_rate(&CONFIG.load(deps.storage)?, &env, closing_time)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let exit_fee = amount
+        .checked_mul_ceil(exit_fee_rate)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    to_json_binary(&WithdrawableNowResponse {
+        amount: amount.checked_sub(exit_fee)?,
+    })
+}
+
+pub fn query_nav(deps: Deps) -> StdResult<Binary> {
+    let (nav, lp_supply) = pool_nav(deps.storage)?;
+    let share_price = if lp_supply.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(nav, lp_supply)
+    };
+
+    to_json_binary(&NavResponse {
+        nav,
+        lp_supply,
+        share_price,
+    })
+}
+
+pub fn query_pending_exit(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+
+    to_json_binary(&PENDING_EXITS.may_load(deps.storage, &address)?)
+}
+
+pub fn query_pending_exits(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+
+    let exits = PENDING_EXITS
+        .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit.map_or(usize::MAX, |limit| limit as usize))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&PendingExitsResponse {
+        exits,
+    })
+}
+
+pub fn query_role_holders(
+    deps: Deps,
+    env: Env,
+    role: Role,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    let now = env.block.time.seconds();
+
+    // expired grants are not listed
+    let holders = ROLES
+        .prefix(role.to_string().as_str())
+        .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, grant)) => !grant.expires_at.is_some_and(|expires_at| expires_at <= now),
+            Err(_) => true,
+        })
+        .take(limit.map_or(usize::MAX, |limit| limit as usize))
+        .map(|item| {
+            item.map(|(address, grant)| RoleHolder {
+                address,
+                expires_at: grant.expires_at,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&RoleHoldersResponse {
+        holders,
+    })
+}
+
+pub fn query_keeper_bond(deps: Deps, address: String) -> StdResult<Binary> {
+    let address = deps.api.addr_validate(&address)?;
+
+    to_json_binary(&KEEPER_BONDS.may_load(deps.storage, &address)?)
+}
+
+pub fn query_keeper_bonds(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+
+    let bonds = KEEPER_BONDS
+        .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit.map_or(usize::MAX, |limit| limit as usize))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&KeeperBondsResponse {
+        bonds,
+    })
+}
+
+pub fn query_keeper_reports(
+    deps: Deps,
+    auction_round: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+
+    let reports = KEEPER_REPORTS
+        .prefix(auction_round)
+        .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit.map_or(usize::MAX, |limit| limit as usize))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&KeeperReportsResponse {
+        reports,
+    })
+}
+
+pub fn query_pending_settlement(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&PENDING_SETTLEMENT.may_load(deps.storage)?)
+}
+
+pub fn query_attestation_required(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&ATTESTATION_REQUIRED.may_load(deps.storage)?)
+}
+
+pub fn query_settlement_queue(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&SETTLEMENT_QUEUE.may_load(deps.storage)?)
+}
+
+pub fn query_settlement_report(deps: Deps, auction_round: u64) -> StdResult<Binary> {
+    to_json_binary(&SETTLEMENT_REPORTS.may_load(deps.storage, auction_round)?)
+}
+
+pub fn query_slashings(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let slashings = cw_paginate_storage::paginate_map(
+        deps,
+        &SLASHINGS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+    )?
+    .into_iter()
+    .map(|(_, slashing)| slashing)
+    .collect();
+
+    to_json_binary(&SlashingsResponse {
+        slashings,
+    })
+}
+
+pub fn query_pause_status(deps: Deps) -> StdResult<Binary> {
+    to_json_binary(&PAUSE_STATUS.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn query_commitments(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+
+    let commitments = COMMITMENTS
+        .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit.map_or(usize::MAX, |limit| limit as usize))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&CommitmentsResponse {
+        commitments,
+    })
+}
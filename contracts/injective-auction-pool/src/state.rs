@@ -1,7 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, Int64, Uint128, Uint64};
+use cosmwasm_std::{Addr, Coin, Empty, Int64, Uint128, Uint64};
 use cw_storage_plus::{Item, Map};
-use injective_auction::auction_pool::Config;
+use injective_auction::auction_pool::{
+    Commitment, Config, ConfigProposal, KeeperBond, KeeperReport, PauseStatus, PendingExit,
+    PendingSettlement, PoolStats, RoundBid, RoundRecord, SettlementQueue, SettlementReport,
+    Slashing,
+};
 
 #[cw_serde]
 pub struct Auction {
@@ -18,18 +22,90 @@ pub struct Auction {
 #[cw_serde]
 pub struct Whitelisted;
 
+#[cw_serde]
+pub struct RoleGrant {
+    /// Time in seconds from which the address no longer holds the role
+    pub expires_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct Deposit {
+    /// The LP subdenom minted for the deposit
+    pub lp_subdenom: u64,
+    /// The amount of native denom deposited
+    pub amount: Uint128,
+}
+
 /// Stores the config of the contract
 pub const CONFIG: Item<Config> = Item::new("config");
+/// Stores the proposed config change waiting for its activation time
+pub const CONFIG_PROPOSAL: Item<ConfigProposal> = Item::new("config_proposal");
 /// Whitelisted addresses that can call TryBid
 pub const WHITELISTED_ADDRESSES: Map<&Addr, Whitelisted> = Map::new("whitelisted_addresses");
+/// Stores the addresses granted each role, keyed by the name of the role
+pub const ROLES: Map<(&str, &Addr), RoleGrant> = Map::new("roles");
+/// Stores the native denom bonded by each keeper to settle the auction
+pub const KEEPER_BONDS: Map<&Addr, KeeperBond> = Map::new("keeper_bonds");
+/// Stores the total bonded and unbonding by the keepers
+pub const TOTAL_KEEPER_BONDS: Item<Uint128> = Item::new("total_keeper_bonds");
+/// Maps the auction round and the keeper to the result it reported, until the round is settled
+pub const KEEPER_REPORTS: Map<(u64, &Addr), KeeperReport> = Map::new("keeper_reports");
+/// Stores the missed auction round the contract bid on, that can only be settled by the owner
+pub const ATTESTATION_REQUIRED: Item<u64> = Item::new("attestation_required");
+/// Stores the settlement reported by a keeper while it can still be disputed
+pub const PENDING_SETTLEMENT: Item<PendingSettlement> = Item::new("pending_settlement");
+/// Maps the auction round to the settlement reported by the keeper that settled it
+pub const SETTLEMENT_REPORTS: Map<u64, SettlementReport> = Map::new("settlement_reports");
+/// Maps the auction round to the evidence of the keeper slashed for reporting it wrong
+pub const SLASHINGS: Map<u64, Slashing> = Map::new("slashings");
 /// Stores the available balance that can be used for bidding
 pub const BIDDING_BALANCE: Item<Uint128> = Item::new("bidding_balance");
+/// Stores the supply of the current LP denom, the shares of the bidding balance
+pub const LP_SUPPLY: Item<Uint128> = Item::new("lp_supply");
 /// Stores the current auction details
 pub const UNSETTLED_AUCTION: Item<Auction> = Item::new("unsettled_auction");
 /// Maps the auction round to the treasure chest contract address
 pub const TREASURE_CHEST_CONTRACTS: Map<u64, Addr> = Map::new("treasure_chest_contracts");
+/// Stores the operations paused by the owner
+pub const PAUSE_STATUS: Item<PauseStatus> = Item::new("pause_status");
 /// Stores whether the funds can be withdrawn or not from the contract
 pub const FUNDS_LOCKED: Item<bool> = Item::new("funds_locked");
+/// Stores the deposits of each address, per auction round
+pub const DEPOSITS: Map<(&Addr, u64), Deposit> = Map::new("deposits");
+/// Stores the amount deposited by each address with a given LP subdenom, so that depositors are
+/// counted without scanning their deposits history
+pub const DEPOSITOR_TOTALS: Map<(&Addr, u64), Uint128> = Map::new("depositor_totals");
+/// Stores the amount deposited by each address on a given auction round, used to list the
+/// depositors of a round
+pub const ROUND_DEPOSITORS: Map<(u64, &Addr), Uint128> = Map::new("round_depositors");
+/// Stores the bids made by the contract on each auction round that has not been settled yet
+pub const ROUND_BIDS: Map<u64, Vec<RoundBid>> = Map::new("round_bids");
+/// Maps the auction round to the record of the settled auction
+pub const ROUND_HISTORY: Map<u64, RoundRecord> = Map::new("round_history");
+/// Stores the lifetime statistics of the pool
+pub const STATS: Item<PoolStats> = Item::new("stats");
+/// Stores the deposits of each address that join the pool on a later auction round, per round
+pub const PENDING_DEPOSITS: Map<(&Addr, u64), Uint128> = Map::new("pending_deposits");
+/// Stores the pending deposits of each auction round, used to activate them when the round starts
+pub const ROUND_PENDING_DEPOSITS: Map<(u64, &Addr), Uint128> = Map::new("round_pending_deposits");
+/// Stores the total amount of the deposits pending for each auction round
+pub const PENDING_DEPOSIT_TOTALS: Map<u64, Uint128> = Map::new("pending_deposit_totals");
+/// Stores the total amount of the deposits held until they join the pool
+pub const TOTAL_PENDING_DEPOSITS: Item<Uint128> = Item::new("total_pending_deposits");
+/// Stores the exits requested while the pool was locked, processed when the auction is settled
+pub const PENDING_EXITS: Map<&Addr, PendingExit> = Map::new("pending_exits");
+/// Stores the total amount of LP tokens escrowed by the pending exits
+pub const TOTAL_PENDING_EXITS: Item<Uint128> = Item::new("total_pending_exits");
+/// Stores the total amount of LP tokens of the pending exits charged as exit fee
+pub const TOTAL_PENDING_EXIT_FEES: Item<Uint128> = Item::new("total_pending_exit_fees");
+/// Stores the multi-round commitment of each address, renewed when the auction is settled
+pub const COMMITMENTS: Map<&Addr, Commitment> = Map::new("commitments");
+/// Stores the commitments by the last auction round they are committed to
+pub const COMMITMENT_EXPIRIES: Map<(u64, &Addr), Empty> = Map::new("commitment_expiries");
+/// Stores the total amount of LP tokens escrowed by the commitments
+pub const TOTAL_COMMITTED: Item<Uint128> = Item::new("total_committed");
+/// Stores what is left to process from the last settlement
+pub const SETTLEMENT_QUEUE: Item<SettlementQueue> = Item::new("settlement_queue");
 
 #[cw_serde]
 #[serde(rename_all = "camelCase")]
@@ -1,37 +1,86 @@
-use std::{marker::PhantomData, str::FromStr};
+use std::{collections::HashMap, marker::PhantomData, str::FromStr};
 
 use cosmwasm_std::{
     attr, coin, coins, from_json,
     testing::{mock_env, mock_info, BankQuerier, MockApi, MockStorage, MOCK_CONTRACT_ADDR},
-    to_json_binary, Addr, BankMsg, Binary, CodeInfoResponse, ContractResult as CwContractResult,
-    CosmosMsg, Decimal, Decimal256, Empty, Env, HexBinary, Int64, MemoryStorage, MessageInfo,
-    OwnedDeps, Querier, QuerierResult, QueryRequest, Uint128, Uint256, Uint64, WasmQuery,
+    to_json_binary, Addr, Api, BankMsg, Binary, CanonicalAddr, CodeInfoResponse,
+    ContractInfoResponse, ContractResult as CwContractResult, CosmosMsg, Decimal, Decimal256,
+    DepsMut, Empty, Env, HexBinary, Int64, MemoryStorage, MessageInfo, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, RecoverPubkeyError, Response, StdResult, Timestamp, Uint128,
+    Uint256, Uint64, VerificationError, WasmMsg, WasmQuery,
 };
 use cw_ownable::Ownership;
 use injective_auction::auction_pool::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, WhitelistedAddressesResponse,
+    BidStrategy, BidWindowResponse, Commitment, CommitmentsResponse, ConfigProposal,
+    ConfigResponse, DenomPriceSource, DepositorsResponse, ExecuteMsg, ExitFeeSchedule,
+    InstantiateMsg, KeeperBond, KeeperBondsResponse, KeeperReport, KeeperReportsResponse,
+    LockStatusResponse, NavResponse, PauseStatus, PendingDeposit, PendingDepositTotalsResponse,
+    PendingDepositsResponse, PendingExit, PendingExitsResponse, PendingSettlement, PoolStats,
+    PriceFeedConfig, PriceSource, PricingConfig, QueryMsg, Role, RoleHolder, RoleHoldersResponse,
+    RoundBid, RoundDeposit, RoundHistoryResponse, RoundRecord, SettlementDispute, SettlementQueue,
+    SettlementReport, Slashing, SlashingsResponse, StatsResponse, SurplusPolicy,
+    UserPositionResponse, WhitelistedAddressesResponse, WithdrawableNowResponse,
 };
 use injective_std::types::cosmos::base::v1beta1::Coin;
-use injective_std::types::injective::auction::v1beta1::MsgBid;
+use injective_std::types::injective::{
+    auction::v1beta1::{
+        LastAuctionResult, MsgBid, Params, QueryAuctionParamsResponse,
+        QueryLastAuctionResultResponse,
+    },
+    exchange::v1beta1::QuerySpotMidPriceAndTobResponse,
+    oracle::v1beta1::{PricePairState, QueryOraclePriceResponse},
+};
 use prost::Message;
 use treasurechest::tf::tokenfactory::TokenFactoryType;
 
 use crate::{
     contract::{execute, instantiate, query},
-    state::{BIDDING_BALANCE, FUNDS_LOCKED},
+    pricing::{FeedPrice, PriceFeed, PriceFeedQueryMsg, PriceFeedResponse},
+    state::{
+        CurrentAuctionBasketResponse, BIDDING_BALANCE, FUNDS_LOCKED, SETTLEMENT_QUEUE,
+        TREASURE_CHEST_CONTRACTS, UNSETTLED_AUCTION,
+    },
     ContractError,
 };
 
 pub struct AuctionQuerier {
-    bank: BankQuerier,
+    pub bank: BankQuerier,
+    pub current_auction: CurrentAuctionBasketResponse,
+    pub last_auction_result: Option<LastAuctionResult>,
+    pub oracle_price: Option<PricePairState>,
+    pub spot_mid_price: String,
+    /// Prices returned by the mocked "pyth" price feed contract
+    pub price_feeds: HashMap<String, FeedPrice>,
 }
 
 impl AuctionQuerier {
     pub fn new() -> AuctionQuerier {
         AuctionQuerier {
             bank: BankQuerier::new(&[]),
+            current_auction: CurrentAuctionBasketResponse {
+                amount: vec![cosmwasm_std::Coin {
+                    denom: "uatom".to_string(),
+                    amount: Uint128::new(10000u128),
+                }],
+                auction_round: Uint64::one(),
+                // simulates now + 7 days in seconds
+                auction_closing_time: Int64::new(1_571_797_419 + 7 * 86_400),
+                highest_bidder: "highest_bidder".to_string(),
+                highest_bid_amount: Uint128::new(20000u128),
+            },
+            last_auction_result: None,
+            oracle_price: None,
+            spot_mid_price: String::new(),
+            price_feeds: HashMap::new(),
         }
     }
+
+    /// Moves the mocked auction module to the given round, with no bids placed yet
+    pub fn start_round(&mut self, auction_round: u64) {
+        self.current_auction.auction_round = Uint64::new(auction_round);
+        self.current_auction.auction_closing_time =
+            Int64::new(1_571_797_419 + 7 * 86_400 * auction_round as i64);
+    }
 }
 
 impl Querier for AuctionQuerier {
@@ -43,27 +92,76 @@ impl Querier for AuctionQuerier {
                 data: _,
             } => match path.as_str() {
                 "/injective.auction.v1beta1.Query/CurrentAuctionBasket" => {
-                    Ok(CwContractResult::Ok(
-                        to_json_binary(&crate::state::CurrentAuctionBasketResponse {
-                            amount: vec![cosmwasm_std::Coin {
-                                denom: "uatom".to_string(),
-                                amount: Uint128::new(10000u128),
-                            }],
-                            auction_round: Uint64::one(),
-                            // simulates now + 7 days in seconds
-                            auction_closing_time: Int64::new(1_571_797_419 + 7 * 86_400),
-                            highest_bidder: "highest_bidder".to_string(),
-                            highest_bid_amount: Uint128::new(20000u128),
-                        })
-                        .unwrap(),
-                    ))
-                    .into()
+                    Ok(CwContractResult::Ok(to_json_binary(&self.current_auction).unwrap())).into()
                 },
+                "/injective.auction.v1beta1.Query/AuctionParams" => Ok(CwContractResult::Ok(
+                    to_json_binary(&QueryAuctionParamsResponse {
+                        params: Some(Params {
+                            auction_period: 7 * 86_400,
+                            min_next_bid_increment_rate: "0.0025".to_string(),
+                        }),
+                    })
+                    .unwrap(),
+                ))
+                .into(),
+                "/injective.auction.v1beta1.Query/LastAuctionResult" => Ok(CwContractResult::Ok(
+                    to_json_binary(&QueryLastAuctionResultResponse {
+                        last_auction_result: self.last_auction_result.clone(),
+                    })
+                    .unwrap(),
+                ))
+                .into(),
+                "/injective.oracle.v1beta1.Query/OraclePrice" => Ok(CwContractResult::Ok(
+                    to_json_binary(&QueryOraclePriceResponse {
+                        price_pair_state: self.oracle_price.clone(),
+                    })
+                    .unwrap(),
+                ))
+                .into(),
+                "/injective.exchange.v1beta1.Query/SpotMidPriceAndTOB" => Ok(CwContractResult::Ok(
+                    to_json_binary(&QuerySpotMidPriceAndTobResponse {
+                        mid_price: self.spot_mid_price.clone(),
+                        best_buy_price: String::new(),
+                        best_sell_price: String::new(),
+                    })
+                    .unwrap(),
+                ))
+                .into(),
                 &_ => QuerierResult::Err(cosmwasm_std::SystemError::UnsupportedRequest {
                     kind: format!("Unmocked stargate query path: {path:?}"),
                 }),
             },
             QueryRequest::Bank(query) => self.bank.query(&query),
+            QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr,
+                msg,
+            }) if contract_addr == "pyth" => {
+                let PriceFeedQueryMsg::PriceFeed {
+                    id,
+                } = from_json(msg).unwrap();
+                match self.price_feeds.get(&id) {
+                    Some(price) => Ok(CwContractResult::Ok(
+                        to_json_binary(&PriceFeedResponse {
+                            price_feed: PriceFeed {
+                                id,
+                                price: price.clone(),
+                            },
+                        })
+                        .unwrap(),
+                    ))
+                    .into(),
+                    None => Ok(CwContractResult::Err(format!("price feed {id} not found"))).into(),
+                }
+            },
+            // every contract is instantiated from code id 1, as the treasure chests
+            QueryRequest::Wasm(WasmQuery::ContractInfo {
+                ..
+            }) => {
+                let mut contract_info = ContractInfoResponse::default();
+                contract_info.code_id = 1;
+                contract_info.creator = MOCK_CONTRACT_ADDR.to_string();
+                Ok(CwContractResult::Ok(to_json_binary(&contract_info).unwrap())).into()
+            },
             QueryRequest::Wasm(WasmQuery::CodeInfo {
                 code_id,
             }) => Ok(CwContractResult::Ok(
@@ -85,18 +183,80 @@ impl Querier for AuctionQuerier {
     }
 }
 
+/// Wraps [MockApi] so that the 20 bytes addresses predicted for the treasure chest contracts can be
+/// humanized
+#[derive(Copy, Clone, Default)]
+pub struct AuctionApi(MockApi);
+
+impl Api for AuctionApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        self.0.addr_validate(human)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        self.0.addr_canonicalize(human)
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        if canonical.len() == 20 {
+            return Ok(Addr::unchecked(HexBinary::from(canonical.as_slice()).to_hex()));
+        }
+        self.0.addr_humanize(canonical)
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.0.secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.0.debug(message)
+    }
+}
+
 pub fn mock_deps_with_querier(
     _info: &MessageInfo,
-) -> OwnedDeps<MockStorage, MockApi, AuctionQuerier, Empty> {
+) -> OwnedDeps<MockStorage, AuctionApi, AuctionQuerier, Empty> {
     OwnedDeps {
         storage: MockStorage::default(),
-        api: MockApi::default(),
+        api: AuctionApi::default(),
         querier: AuctionQuerier::new(),
         custom_query_type: PhantomData,
     }
 }
 
-pub fn init() -> (OwnedDeps<MemoryStorage, MockApi, AuctionQuerier>, Env) {
+pub fn init() -> (OwnedDeps<MemoryStorage, AuctionApi, AuctionQuerier>, Env) {
     let info = mock_info("instantiator", &coins(2, "native_denom"));
     let mut deps = mock_deps_with_querier(&info);
     let env = mock_env();
@@ -112,6 +272,19 @@ pub fn init() -> (OwnedDeps<MemoryStorage, MockApi, AuctionQuerier>, Env) {
         min_next_bid_increment_rate: Decimal::from_ratio(25u128, 10_000u128),
         treasury_chest_code_id: 1,
         min_return: Decimal::percent(5),
+        pricing: None,
+        bid_strategy: None,
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        exit_fee: None,
+        surplus_policy: None,
+        config_timelock: None,
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: None,
+        settlement_quorum: None,
+        min_deposit: None,
     };
     let res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
 
@@ -139,6 +312,23 @@ pub fn init() -> (OwnedDeps<MemoryStorage, MockApi, AuctionQuerier>, Env) {
     (deps, env)
 }
 
+/// Proposes a config change and applies it once its activation time has passed
+fn apply_config_change(
+    mut deps: DepsMut,
+    mut env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    execute(deps.branch(), env.clone(), info.clone(), msg)?;
+
+    let proposal: Option<ConfigProposal> =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::ConfigProposal {}).unwrap()).unwrap();
+    let activates_at = proposal.unwrap().activates_at;
+    env.block.time = Timestamp::from_seconds(activates_at.max(env.block.time.seconds()));
+
+    execute(deps, env, info, ExecuteMsg::ApplyConfig {})
+}
+
 #[test]
 fn update_config() {
     let (mut deps, env) = init();
@@ -146,21 +336,31 @@ fn update_config() {
     // update config as non-owner should fail
     let info = mock_info("not_owner", &[]);
     let msg = ExecuteMsg::UpdateConfig {
-        rewards_fee: None,
-        rewards_fee_addr: None,
         min_next_bid_increment_rate: None,
-        min_return: None,
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: None,
+        settlement_quorum: None,
+        min_deposit: None,
     };
     let res = execute(deps.as_mut().branch(), env.clone(), info, msg.clone()).unwrap_err();
-    assert_eq!(res, ContractError::Ownership(cw_ownable::OwnershipError::NotOwner));
+    assert_eq!(res, ContractError::Unauthorized {});
 
     // update some of the config fields as owner should work
     let info = mock_info("owner", &[]);
     let msg = ExecuteMsg::UpdateConfig {
-        rewards_fee: Some(Decimal::percent(20)),
-        rewards_fee_addr: Some("new_rewards_addr".to_string()),
         min_next_bid_increment_rate: Some(Decimal::percent(10)),
-        min_return: Some(Decimal::percent(10)),
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: None,
+        settlement_quorum: None,
+        min_deposit: None,
     };
     let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
     assert_eq!(
@@ -169,11 +369,28 @@ fn update_config() {
             attr("action", "update_config"),
             attr("native_denom", "native_denom"),
             attr("token_factory_type", "Injective"),
-            attr("rewards_fee", "0.2"),
-            attr("rewards_fee_addr", "new_rewards_addr"),
+            attr("rewards_fee", "0.1"),
+            attr("rewards_fee_addr", "rewards_addr"),
             attr("min_next_bid_increment_rate", "0.1"),
             attr("treasury_chest_code_id", "1"),
-            attr("min_return", "0.1"),
+            attr("min_return", "0.05"),
+            attr("price_sources", "0"),
+            attr("max_price_age", "0"),
+            attr("basket_value_tolerance", "0"),
+            attr("bid_strategy", "minimal_increment"),
+            attr("bid_window", "0"),
+            attr("lock_period_seconds", "0"),
+            attr("rebid_buffer", "0"),
+            attr("exit_fee_base", "0"),
+            attr("exit_fee_max", "0"),
+            attr("exit_fee_ramp_seconds", "0"),
+            attr("surplus_policy", "to_chest"),
+            attr("config_timelock", "0"),
+            attr("keeper_min_bond", "0"),
+            attr("keeper_unbonding_period", "0"),
+            attr("settlement_challenge_period", "0"),
+            attr("settlement_quorum", "1"),
+            attr("min_deposit", "0"),
         ]
     );
 
@@ -181,10 +398,149 @@ fn update_config() {
     let msg = QueryMsg::Config {};
     let res: ConfigResponse = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
     let config = res.config;
-    assert_eq!(config.rewards_fee, Decimal::percent(20));
-    assert_eq!(config.rewards_fee_addr, "new_rewards_addr".to_string());
     assert_eq!(config.min_next_bid_increment_rate, Decimal::percent(10));
-    assert_eq!(config.min_return, Decimal::percent(10));
+}
+
+#[test]
+fn propose_and_apply_config() {
+    let (mut deps, mut env) = init();
+
+    // proposing a config change as non-owner should fail
+    let msg = ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: None,
+        exit_fee: None,
+        surplus_policy: None,
+        treasury_chest_code_id: None,
+        config_timelock: Some(3_600),
+    };
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), mock_info("not_owner", &[]), msg.clone())
+            .unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    // without a timelock the change can be applied right away
+    let info = mock_info("owner", &[]);
+    execute(deps.as_mut().branch(), env.clone(), info.clone(), msg).unwrap();
+    execute(deps.as_mut().branch(), env.clone(), info.clone(), ExecuteMsg::ApplyConfig {}).unwrap();
+
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), info.clone(), ExecuteMsg::ApplyConfig {})
+            .unwrap_err();
+    assert_eq!(res, ContractError::NoConfigProposal {});
+
+    // lowering the fee waits for the config timelock
+    let now = env.block.time.seconds();
+    let msg = ExecuteMsg::ProposeConfig {
+        rewards_fee: Some(Decimal::percent(5)),
+        rewards_fee_addr: Some("new_rewards_addr".to_string()),
+        min_return: Some(Decimal::percent(10)),
+        pricing: None,
+        bid_strategy: None,
+        exit_fee: None,
+        surplus_policy: None,
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info.clone(), msg).unwrap();
+
+    let res: Option<ConfigProposal> =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::ConfigProposal {}).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        Some(ConfigProposal {
+            rewards_fee: Some(Decimal::percent(5)),
+            rewards_fee_addr: Some(Addr::unchecked("new_rewards_addr")),
+            min_return: Some(Decimal::percent(10)),
+            pricing: None,
+            bid_strategy: None,
+            exit_fee: None,
+            surplus_policy: None,
+            treasury_chest_code_id: None,
+            config_timelock: None,
+            proposed_at: now,
+            activates_at: now + 3_600,
+        })
+    );
+
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), info.clone(), ExecuteMsg::ApplyConfig {})
+            .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::ConfigProposalNotActive {
+            activates_at: now + 3_600,
+        }
+    );
+
+    env.block.time = Timestamp::from_seconds(now + 3_600);
+    execute(deps.as_mut().branch(), env.clone(), info.clone(), ExecuteMsg::ApplyConfig {}).unwrap();
+
+    let res: ConfigResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(res.config.rewards_fee, Decimal::percent(5));
+    assert_eq!(res.config.rewards_fee_addr, "new_rewards_addr".to_string());
+    assert_eq!(res.config.min_return, Decimal::percent(10));
+
+    // raising the fee waits for at least a full auction period
+    let now = env.block.time.seconds();
+    let msg = ExecuteMsg::ProposeConfig {
+        rewards_fee: Some(Decimal::percent(100)),
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: None,
+        exit_fee: None,
+        surplus_policy: None,
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(res.attributes.last().unwrap(), attr("activates_at", (now + 604_800).to_string()));
+
+    // the proposal can be withdrawn
+    execute(deps.as_mut().branch(), env.clone(), info.clone(), ExecuteMsg::CancelConfigProposal {})
+        .unwrap();
+    let res: Option<ConfigProposal> =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::ConfigProposal {}).unwrap()).unwrap();
+    assert_eq!(res, None);
+
+    // raising the exit fee waits for at least a full auction period as well, and the bid strategy
+    // only changes once the proposal is applied
+    let msg = ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: Some(BidStrategy::MaxAllowed),
+        exit_fee: Some(ExitFeeSchedule {
+            base_fee: Decimal::percent(1),
+            max_fee: Decimal::percent(1),
+            ramp_seconds: 0,
+        }),
+        surplus_policy: Some(SurplusPolicy::RollOver),
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(res.attributes.last().unwrap(), attr("activates_at", (now + 604_800).to_string()));
+
+    let res: ConfigResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(res.config.bid_strategy, BidStrategy::MinimalIncrement);
+    assert_eq!(res.config.exit_fee, ExitFeeSchedule::default());
+
+    env.block.time = Timestamp::from_seconds(now + 604_800);
+    execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::ApplyConfig {}).unwrap();
+
+    let res: ConfigResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(res.config.bid_strategy, BidStrategy::MaxAllowed);
+    assert_eq!(res.config.exit_fee.base_fee, Decimal::percent(1));
+    assert_eq!(res.config.surplus_policy, SurplusPolicy::RollOver);
 }
 
 #[test]
@@ -235,7 +591,7 @@ fn add_whitelist_address() {
         add: vec!["new_whitelisted".to_string()],
     };
     let res = execute(deps.as_mut().branch(), env.clone(), info, msg.clone()).unwrap_err();
-    assert_eq!(res, ContractError::Ownership(cw_ownable::OwnershipError::NotOwner));
+    assert_eq!(res, ContractError::Unauthorized {});
 
     // whitelist addresses as owner should work
     let info = mock_info("owner", &[]);
@@ -295,7 +651,7 @@ fn remove_whitelisted_address() {
         add: vec![],
     };
     let res = execute(deps.as_mut().branch(), env.clone(), info, msg.clone()).unwrap_err();
-    assert_eq!(res, ContractError::Ownership(cw_ownable::OwnershipError::NotOwner));
+    assert_eq!(res, ContractError::Unauthorized {});
 
     // remove whitelisted address as owner should work
     let info = mock_info("owner", &[]);
@@ -330,6 +686,123 @@ fn remove_whitelisted_address() {
     assert_eq!(res.addresses, vec![Addr::unchecked("new_whitelisted").to_string()]);
 }
 
+#[test]
+fn grant_and_revoke_roles() {
+    let (mut deps, mut env) = init();
+    let owner = mock_info("owner", &[]);
+    let now = env.block.time.seconds();
+
+    // admin roles are granted by the owner only
+    for (role, address, expires_at) in
+        [(Role::KeeperManager, "manager", None), (Role::Pauser, "pauser", Some(now + 10))]
+    {
+        let msg = ExecuteMsg::GrantRole {
+            role,
+            address: address.to_string(),
+            expires_at,
+        };
+        execute(deps.as_mut().branch(), env.clone(), owner.clone(), msg).unwrap();
+    }
+
+    let msg = ExecuteMsg::GrantRole {
+        role: Role::ConfigAdmin,
+        address: "manager".to_string(),
+        expires_at: None,
+    };
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), mock_info("manager", &[]), msg).unwrap_err();
+    assert_eq!(res, ContractError::Ownership(cw_ownable::OwnershipError::NotOwner));
+
+    // keeper managers grant the bidder and settler roles
+    let manager = mock_info("manager", &[]);
+    let msg = ExecuteMsg::GrantRole {
+        role: Role::Bidder,
+        address: "hot_bot".to_string(),
+        expires_at: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), manager.clone(), msg).unwrap();
+
+    let msg = QueryMsg::RoleHolders {
+        role: Role::Bidder,
+        start_after: None,
+        limit: None,
+    };
+    let res: RoleHoldersResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res.holders,
+        vec![RoleHolder {
+            address: Addr::unchecked("hot_bot"),
+            expires_at: None,
+        }]
+    );
+
+    // the bidder can bid but not settle the auction
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let hot_bot = mock_info("hot_bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), hot_bot.clone(), msg.clone()).unwrap();
+
+    deps.querier.start_round(2);
+    let settle = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), hot_bot.clone(), settle).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    // the role can be revoked by the keeper managers
+    let revoke = ExecuteMsg::RevokeRole {
+        role: Role::Bidder,
+        address: "hot_bot".to_string(),
+    };
+    execute(deps.as_mut().branch(), env.clone(), manager.clone(), revoke.clone()).unwrap();
+    let res = execute(deps.as_mut().branch(), env.clone(), manager, revoke).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::RoleNotGranted {
+            role: "bidder".to_string(),
+            address: "hot_bot".to_string(),
+        }
+    );
+
+    // roles are not held once expired
+    let pause = ExecuteMsg::UpdatePauseStatus {
+        join: Some(true),
+        exit: None,
+        bid: None,
+        settle: None,
+    };
+    let pauser = mock_info("pauser", &[]);
+    execute(deps.as_mut().branch(), env.clone(), pauser.clone(), pause.clone()).unwrap();
+
+    env.block.time = Timestamp::from_seconds(now + 10);
+    let res = execute(deps.as_mut().branch(), env.clone(), pauser, pause).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let msg = QueryMsg::RoleHolders {
+        role: Role::Pauser,
+        start_after: None,
+        limit: None,
+    };
+    let res: RoleHoldersResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert!(res.holders.is_empty());
+}
+
 #[test]
 pub fn join_pool_works() {
     let (mut deps, env) = init();
@@ -337,6 +810,7 @@ pub fn join_pool_works() {
     let info = mock_info("robinho", &coins(100, "native_denom"));
     let msg = ExecuteMsg::JoinPool {
         auction_round: 1,
+        rounds: None,
     };
     let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
 
@@ -383,6 +857,7 @@ fn join_pool_fails() {
     let info = mock_info("robinho", &coins(100, "wrong_denom"));
     let msg = ExecuteMsg::JoinPool {
         auction_round: 1,
+        rounds: None,
     };
     let res = execute(deps.as_mut().branch(), env.clone(), info, msg.clone()).unwrap_err();
     assert_eq!(
@@ -402,17 +877,18 @@ fn join_pool_fails() {
     let res = execute(deps.as_mut().branch(), env.clone(), info, msg.clone()).unwrap_err();
     assert_eq!(res, ContractError::PaymentError(cw_utils::PaymentError::MultipleDenoms {}));
 
-    // joining the wrong auction round should fail
+    // joining a past auction round should fail
     let info = mock_info("robinho", &coins(100, "native_denom"));
     let msg = ExecuteMsg::JoinPool {
-        auction_round: 2,
+        auction_round: 0,
+        rounds: None,
     };
     let res = execute(deps.as_mut().branch(), env.clone(), info, msg.clone()).unwrap_err();
     assert_eq!(
         res,
         ContractError::InvalidAuctionRound {
             current_auction_round: 1,
-            auction_round: 2
+            auction_round: 0
         }
     );
 }
@@ -424,6 +900,7 @@ fn exit_pool_works() {
     let info = mock_info("robinho", &coins(100, "native_denom"));
     let msg = ExecuteMsg::JoinPool {
         auction_round: 1,
+        rounds: None,
     };
     let _ = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
 
@@ -454,7 +931,7 @@ fn exit_pool_works() {
     );
 
     // checking attributes are fine
-    assert_eq!(res.attributes, vec![attr("action", "exit_pool")]);
+    assert_eq!(res.attributes, vec![attr("action", "exit_pool"), attr("exit_fee", "0")]);
 }
 
 #[test]
@@ -464,6 +941,7 @@ fn exit_pool_fails() {
     let info = mock_info("robinho", &coins(100, "native_denom"));
     let msg = ExecuteMsg::JoinPool {
         auction_round: 1,
+        rounds: None,
     };
     let _ = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg).unwrap();
 
@@ -503,7 +981,7 @@ fn exit_pool_fails() {
         }
         .into()
     );
-    assert_eq!(res.attributes, vec![attr("action", "exit_pool")]);
+    assert_eq!(res.attributes, vec![attr("action", "exit_pool"), attr("exit_fee", "0")]);
 
     // exit pool after the contract bid should fail
     FUNDS_LOCKED.save(deps.as_mut().storage, &true).unwrap();
@@ -512,292 +990,3432 @@ fn exit_pool_fails() {
 }
 
 #[test]
-fn try_bid_works() {
+fn exit_pool_within_lock_period_fails() {
     let (mut deps, mut env) = init();
 
-    // try bid with no previous bids should not fail but won't bid either
-    let info = mock_info("bot", &[]);
-    let msg = ExecuteMsg::TryBid {
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: None,
+        lock_period_seconds: Some(86_400),
+        rebid_buffer: None,
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: None,
+        settlement_quorum: None,
+        min_deposit: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("robinho", &coins(100, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
         auction_round: 1,
-        basket_value: Uint128::from(10_000u128),
+        rounds: None,
     };
-    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap();
-    assert_eq!(res.messages.len(), 0);
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let closing_time = UNSETTLED_AUCTION.load(&deps.storage).unwrap().closing_time;
+    let msg = QueryMsg::LockStatus {};
+    let res: LockStatusResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
     assert_eq!(
-        res.attributes,
-        vec![
-            attr("action", "did_not_bid"),
-            attr("reason", "minimum_allowed_bid_is_higher_than_bidding_balance")
-        ]
+        res,
+        LockStatusResponse {
+            lock_period_seconds: 86_400,
+            lock_starts_at: closing_time - 86_400,
+            lock_ends_at: closing_time,
+            bid_locked: false,
+            time_locked: false,
+            is_locked: false,
+        }
     );
 
-    // join pool with one user & enough funds to be able to outbid default highest bid (20000)
-    let info = mock_info("robinho", &coins(30_000, "native_denom"));
-    let msg = ExecuteMsg::JoinPool {
-        auction_round: 1,
+    // exit pool within the lock period should fail even if the contract has not bid
+    env.block.time = Timestamp::from_seconds(closing_time - 3_600);
+    let res: LockStatusResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert!(res.is_locked);
+
+    let info =
+        mock_info("robinho", &coins(100, format!("factory/{}/auction.0", env.contract.address)));
+    let exit = ExecuteMsg::ExitPool {};
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), exit.clone()).unwrap_err();
+    assert_eq!(res, ContractError::PooledAuctionLocked {});
+
+    // exit pool after the auction closed should work as the contract has not bid
+    env.block.time = Timestamp::from_seconds(closing_time);
+    let res = execute(deps.as_mut().branch(), env.clone(), info, exit).unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "exit_pool"), attr("exit_fee", "0")]);
+}
+
+#[test]
+fn paused_operations_fail() {
+    let (mut deps, mut env) = init();
+
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: None,
+        lock_period_seconds: Some(86_400),
+        rebid_buffer: None,
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: None,
+        settlement_quorum: None,
+        min_deposit: None,
     };
-    let _ = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
 
-    let info = mock_info("bot", &[]);
-    let msg = ExecuteMsg::TryBid {
+    let join = ExecuteMsg::JoinPool {
         auction_round: 1,
-        basket_value: Uint128::from(100_000u128),
+        rounds: None,
     };
-    let res = execute(deps.as_mut().branch(), env.clone(), info, msg.clone()).unwrap();
+    let info = mock_info("robinho", &coins(100, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, join.clone()).unwrap();
 
-    // check the stargate bid message is correct, should only bid minimum allowed bid amount
+    // only the owner can pause the contract
+    let pause = ExecuteMsg::UpdatePauseStatus {
+        join: Some(true),
+        exit: None,
+        bid: Some(true),
+        settle: Some(true),
+    };
+    let info = mock_info("robinho", &[]);
+    let res = execute(deps.as_mut().branch(), env.clone(), info, pause.clone()).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let info = mock_info("owner", &[]);
+    execute(deps.as_mut().branch(), env.clone(), info, pause).unwrap();
 
+    let res: PauseStatus =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::PauseStatus {}).unwrap()).unwrap();
     assert_eq!(
-        res.messages[0].msg,
-        CosmosMsg::Stargate {
-            type_url: "/injective.auction.v1beta1.MsgBid".to_string(),
-            value: {
-                let msg = MsgBid {
-                    sender: env.contract.address.to_string(),
-                    bid_amount: Some(Coin {
-                        denom: "native_denom".to_string(),
-                        amount: "20051".to_string(),
-                    }),
-                    round: 1,
-                };
-                Binary(msg.encode_to_vec())
-            },
+        res,
+        PauseStatus {
+            join: true,
+            exit: false,
+            bid: true,
+            settle: true,
         }
     );
-    assert_eq!(res.attributes, vec![attr("action", "try_bid"), attr("amount", "20051"),]);
 
-    assert!(FUNDS_LOCKED.load(&deps.storage).unwrap());
+    let info = mock_info("robinho", &coins(100, "native_denom"));
+    let res = execute(deps.as_mut().branch(), env.clone(), info, join).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Paused {
+            operation: "join".to_string(),
+        }
+    );
 
-    // try bid on a basket value that is lower than the highest bid should not fail but not bid
-    // either
     let info = mock_info("bot", &[]);
     let msg = ExecuteMsg::TryBid {
         auction_round: 1,
-        basket_value: Uint128::from(5_000u128),
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
     };
-    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap();
-    assert_eq!(res.messages.len(), 0);
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap_err();
     assert_eq!(
-        res.attributes,
-        vec![
-            attr("action", "did_not_bid"),
-            attr("reason", "basket_value_is_not_worth_bidding_for")
-        ]
+        res,
+        ContractError::Paused {
+            operation: "bid".to_string(),
+        }
     );
 
-    // try bid as the highest_bidder should not fail but won't bid either
-    env.contract.address = Addr::unchecked("highest_bidder");
-    let info = mock_info("highest_bidder", &[]);
-    let msg = ExecuteMsg::TryBid {
+    deps.querier.start_round(2);
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::SettleAuction {
         auction_round: 1,
-        basket_value: Uint128::from(100_000u128),
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
     };
-    let res = execute(deps.as_mut().branch(), env.clone(), info, msg.clone()).unwrap();
-    assert_eq!(res.messages.len(), 0);
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap_err();
     assert_eq!(
-        res.attributes,
-        vec![
-            attr("action", "did_not_bid"),
-            attr("reason", "contract_is_already_the_highest_bidder")
-        ]
+        res,
+        ContractError::Paused {
+            operation: "settle".to_string(),
+        }
+    );
+
+    // users can still exit within the lock period while bidding is paused
+    let closing_time = UNSETTLED_AUCTION.load(&deps.storage).unwrap().closing_time;
+    env.block.time = Timestamp::from_seconds(closing_time - 3_600);
+    let res: LockStatusResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::LockStatus {}).unwrap()).unwrap();
+    assert!(!res.is_locked);
+
+    let info =
+        mock_info("robinho", &coins(50, format!("factory/{}/auction.0", env.contract.address)));
+    let exit = ExecuteMsg::ExitPool {};
+    execute(deps.as_mut().branch(), env.clone(), info.clone(), exit.clone()).unwrap();
+
+    // unless exits are paused too
+    let msg = ExecuteMsg::UpdatePauseStatus {
+        join: None,
+        exit: Some(true),
+        bid: None,
+        settle: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+    let res = execute(deps.as_mut().branch(), env.clone(), info, exit).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::Paused {
+            operation: "exit".to_string(),
+        }
     );
 }
 
 #[test]
-fn try_bid_fails() {
+fn exit_pool_withdraws_unbid_surplus() {
     let (mut deps, env) = init();
 
-    // join pool with one user & enough funds to be able to outbid default highest bid (20000)
-    let info = mock_info("robinho", &coins(30_000, "native_denom"));
-    let msg = ExecuteMsg::JoinPool {
-        auction_round: 1,
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: Some(Decimal::percent(10)),
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: None,
+        settlement_quorum: None,
+        min_deposit: None,
     };
-    let _ = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    execute(deps.as_mut().branch(), env.clone(), info.clone(), msg).unwrap();
 
-    // try bid from non-whitelisted address should fail
-    let info = mock_info("non_whitelisted", &[]);
-    let msg = ExecuteMsg::TryBid {
-        auction_round: 1,
-        basket_value: Uint128::from(10_000u128),
+    let msg = ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: None,
+        exit_fee: Some(ExitFeeSchedule {
+            base_fee: Decimal::percent(1),
+            max_fee: Decimal::percent(1),
+            ramp_seconds: 0,
+        }),
+        surplus_policy: None,
+        treasury_chest_code_id: None,
+        config_timelock: None,
     };
-    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg);
-    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+    apply_config_change(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
 
-    // try_bid with wrong auction round should fail
+    for (user, amount) in [("robinho", 30_000u128), ("ronaldinho", 10_000)] {
+        let info = mock_info(user, &coins(amount, "native_denom"));
+        let msg = ExecuteMsg::JoinPool {
+            auction_round: 1,
+            rounds: None,
+        };
+        execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    }
+
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // 20_051 are bid and 2_006 kept for re-bids, so 17_943 out of 40_000 can be withdrawn, less
+    // the exit fee of 135
+    let lp_denom = format!("factory/{}/auction.0", env.contract.address);
+    deps.querier.bank.update_balance("robinho", coins(30_000, lp_denom.as_str()));
+    let msg = QueryMsg::WithdrawableNow {
+        address: "robinho".to_string(),
+    };
+    let res: WithdrawableNowResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(res.amount, Uint128::from(13_322u128));
+
+    // nothing can be withdrawn while exits are paused
+    let owner = mock_info("owner", &[]);
+    let pause_exits = |exit: bool| ExecuteMsg::UpdatePauseStatus {
+        join: None,
+        exit: Some(exit),
+        bid: None,
+        settle: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), owner.clone(), pause_exits(true)).unwrap();
+    let res: WithdrawableNowResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.amount, Uint128::zero());
+    execute(deps.as_mut().branch(), env.clone(), owner, pause_exits(false)).unwrap();
+
+    let info = mock_info("robinho", &coins(30_000, lp_denom.as_str()));
+    let res = execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::ExitPool {}).unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|msg| msg.msg).collect::<Vec<_>>(),
+        vec![
+            TokenFactoryType::Injective.burn(
+                env.contract.address.clone(),
+                lp_denom.as_str(),
+                Uint128::from(13_457u128),
+            ),
+            BankMsg::Send {
+                to_address: "robinho".to_string(),
+                amount: coins(13_322, "native_denom"),
+            }
+            .into(),
+            BankMsg::Send {
+                to_address: "robinho".to_string(),
+                amount: coins(16_543, lp_denom.as_str()),
+            }
+            .into(),
+        ]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "exit_pool"),
+            attr("exit_fee", "135"),
+            attr("withdrawn", "13457"),
+            attr("returned", "16543")
+        ]
+    );
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(26_678u128));
+
+    // while the settlement lags behind, the bids of every unsettled round are reserved
+    deps.querier.start_round(2);
     let info = mock_info("bot", &[]);
     let msg = ExecuteMsg::TryBid {
         auction_round: 2,
-        basket_value: Uint128::from(10_000u128),
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
     };
-    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    deps.querier.bank.update_balance("ronaldinho", coins(10_000, lp_denom.as_str()));
+    let msg = QueryMsg::WithdrawableNow {
+        address: "ronaldinho".to_string(),
+    };
+    let res: WithdrawableNowResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.amount, Uint128::zero());
+
+    let info = mock_info("ronaldinho", &coins(10_000, lp_denom.as_str()));
+    let err = execute(deps.as_mut().branch(), env, info, ExecuteMsg::ExitPool {}).unwrap_err();
+    assert_eq!(err, ContractError::PooledAuctionLocked);
+}
+
+#[test]
+fn exit_pool_charges_exit_fee() {
+    let (mut deps, mut env) = init();
+
+    let update_exit_fee = |exit_fee: ExitFeeSchedule| ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: None,
+        exit_fee: Some(exit_fee),
+        surplus_policy: None,
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+
+    // a max fee lower than the base fee should fail
+    let owner = mock_info("owner", &[]);
+    let res = apply_config_change(
+        deps.as_mut().branch(),
+        env.clone(),
+        owner.clone(),
+        update_exit_fee(ExitFeeSchedule {
+            base_fee: Decimal::percent(5),
+            max_fee: Decimal::percent(1),
+            ramp_seconds: 0,
+        }),
+    )
+    .unwrap_err();
     assert_eq!(
         res,
-        ContractError::InvalidAuctionRound {
-            current_auction_round: 1,
-            auction_round: 2
+        ContractError::InvalidExitFeeSchedule {
+            reason: "max fee must not be lower than the base fee".to_string()
         }
     );
 
-    // try_bid with funds should fail
-    let info = mock_info("bot", &coins(20_000, "native_denom"));
+    apply_config_change(
+        deps.as_mut().branch(),
+        env.clone(),
+        owner,
+        update_exit_fee(ExitFeeSchedule {
+            base_fee: Decimal::percent(1),
+            max_fee: Decimal::percent(5),
+            ramp_seconds: 2 * 86_400,
+        }),
+    )
+    .unwrap();
+
+    for user in ["robinho", "ronaldinho"] {
+        let info = mock_info(user, &coins(10_000, "native_denom"));
+        let msg = ExecuteMsg::JoinPool {
+            auction_round: 1,
+            rounds: None,
+        };
+        execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    }
+
+    // exiting before the fee starts growing charges the base fee
+    let closing_time = UNSETTLED_AUCTION.load(&deps.storage).unwrap().closing_time;
+    env.block.time = Timestamp::from_seconds(closing_time - 3 * 86_400);
+    let lp_denom = format!("factory/{}/auction.0", env.contract.address);
+    let info = mock_info("robinho", &coins(10_000, lp_denom.as_str()));
+    let res = execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::ExitPool {}).unwrap();
+    assert_eq!(
+        res.messages[1].msg,
+        BankMsg::Send {
+            to_address: "robinho".to_string(),
+            amount: coins(9_900, "native_denom"),
+        }
+        .into()
+    );
+    assert_eq!(res.attributes, vec![attr("action", "exit_pool"), attr("exit_fee", "100")]);
+
+    // halfway through the ramp the fee is halfway between the base and max fees, charged on the
+    // 10_100 the remaining LP tokens are now worth
+    env.block.time = Timestamp::from_seconds(closing_time - 86_400);
+    let info = mock_info("ronaldinho", &coins(10_000, lp_denom.as_str()));
+    let res = execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::ExitPool {}).unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "exit_pool"), attr("exit_fee", "303")]);
+
+    // the fees are kept in the bidding balance
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(303u128));
+}
+
+#[test]
+fn join_pool_mints_shares_at_nav() {
+    let (mut deps, env) = init();
+
+    let join_pool = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    let info = mock_info("robinho", &coins(10_000, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, join_pool.clone()).unwrap();
+
+    // fees kept in the pool raise the share price
+    BIDDING_BALANCE.save(deps.as_mut().storage, &Uint128::from(12_500u128)).unwrap();
+    let res: NavResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::Nav {}).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        NavResponse {
+            nav: Uint128::from(12_500u128),
+            lp_supply: Uint128::from(10_000u128),
+            share_price: Decimal::from_ratio(5u128, 4u128),
+        }
+    );
+
+    // joining mints the LP tokens at the share price
+    let info = mock_info("ronaldinho", &coins(5_000, "native_denom"));
+    let res = execute(deps.as_mut().branch(), env.clone(), info, join_pool).unwrap();
+    let lp_denom = format!("factory/{}/auction.0", env.contract.address);
+    assert_eq!(
+        res.messages[0].msg,
+        TokenFactoryType::Injective.mint(
+            env.contract.address.clone(),
+            lp_denom.as_str(),
+            Uint128::from(4_000u128),
+        )
+    );
+
+    // exiting pays the LP tokens at the share price
+    let info = mock_info("robinho", &coins(10_000, lp_denom.as_str()));
+    let res = execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::ExitPool {}).unwrap();
+    assert_eq!(
+        res.messages[1].msg,
+        BankMsg::Send {
+            to_address: "robinho".to_string(),
+            amount: coins(12_500, "native_denom"),
+        }
+        .into()
+    );
+
+    let res: NavResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::Nav {}).unwrap()).unwrap();
+    assert_eq!(res.nav, Uint128::from(5_000u128));
+    assert_eq!(res.lp_supply, Uint128::from(4_000u128));
+}
+
+#[test]
+fn user_position_and_depositors() {
+    let (mut deps, env) = init();
+
+    for (user, amount) in [("robinho", 100u128), ("ronaldinho", 50), ("robinho", 25)] {
+        let info = mock_info(user, &coins(amount, "native_denom"));
+        let msg = ExecuteMsg::JoinPool {
+            auction_round: 1,
+            rounds: None,
+        };
+        execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    }
+
+    let msg = QueryMsg::UserPosition {
+        address: "robinho".to_string(),
+    };
+    let res: UserPositionResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        UserPositionResponse {
+            deposits: vec![RoundDeposit {
+                auction_round: 1,
+                lp_subdenom: 0,
+                amount: Uint128::from(125u128),
+            }],
+            active_amount: Uint128::from(125u128),
+        }
+    );
+
+    let msg = QueryMsg::Depositors {
+        round: 1,
+        start_after: None,
+        limit: None,
+    };
+    let res: DepositorsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res.depositors,
+        vec![
+            (Addr::unchecked("robinho"), Uint128::from(125u128)),
+            (Addr::unchecked("ronaldinho"), Uint128::from(50u128)),
+        ]
+    );
+
+    let msg = QueryMsg::Depositors {
+        round: 1,
+        start_after: Some("robinho".to_string()),
+        limit: Some(1),
+    };
+    let res: DepositorsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.depositors, vec![(Addr::unchecked("ronaldinho"), Uint128::from(50u128))]);
+
+    // exiting the pool reduces the user's position
+    let info =
+        mock_info("robinho", &coins(100, format!("factory/{}/auction.0", env.contract.address)));
+    execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::ExitPool {}).unwrap();
+
+    let msg = QueryMsg::UserPosition {
+        address: "robinho".to_string(),
+    };
+    let res: UserPositionResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.active_amount, Uint128::from(25u128));
+
+    // exiting with LP tokens received from someone else does not underflow the ledger
+    let info =
+        mock_info("ronaldinho", &coins(75, format!("factory/{}/auction.0", env.contract.address)));
+    execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::ExitPool {}).unwrap();
+
+    let msg = QueryMsg::Depositors {
+        round: 1,
+        start_after: None,
+        limit: None,
+    };
+    let res: DepositorsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.depositors, vec![(Addr::unchecked("robinho"), Uint128::from(25u128))]);
+}
+
+#[test]
+fn try_bid_works() {
+    let (mut deps, mut env) = init();
+
+    // try bid with no previous bids should not fail but won't bid either
+    let info = mock_info("bot", &[]);
     let msg = ExecuteMsg::TryBid {
         auction_round: 1,
-        basket_value: Uint128::from(10_000u128),
+        basket_value: Some(Uint128::from(10_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
     };
-    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap_err();
-    assert_eq!(res, ContractError::PaymentError(cw_utils::PaymentError::NonPayable {}));
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "did_not_bid"),
+            attr("reason", "minimum_allowed_bid_is_higher_than_bidding_balance")
+        ]
+    );
+
+    // join pool with one user & enough funds to be able to outbid default highest bid (20000)
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    let _ = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg.clone()).unwrap();
+
+    // check the stargate bid message is correct, should only bid minimum allowed bid amount
+
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Stargate {
+            type_url: "/injective.auction.v1beta1.MsgBid".to_string(),
+            value: {
+                let msg = MsgBid {
+                    sender: env.contract.address.to_string(),
+                    bid_amount: Some(Coin {
+                        denom: "native_denom".to_string(),
+                        amount: "20051".to_string(),
+                    }),
+                    round: 1,
+                };
+                Binary(msg.encode_to_vec())
+            },
+        }
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "try_bid"),
+            attr("amount", "20051"),
+            attr("bid_strategy", "minimal_increment")
+        ]
+    );
+
+    assert!(FUNDS_LOCKED.load(&deps.storage).unwrap());
+
+    // try bid on a basket value that is lower than the highest bid should not fail but not bid
+    // either
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(5_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "did_not_bid"),
+            attr("reason", "basket_value_is_not_worth_bidding_for")
+        ]
+    );
+
+    // try bid as the highest_bidder should not fail but won't bid either
+    env.contract.address = Addr::unchecked("highest_bidder");
+    let info = mock_info("highest_bidder", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg.clone()).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "did_not_bid"),
+            attr("reason", "contract_is_already_the_highest_bidder")
+        ]
+    );
+}
+
+#[test]
+fn try_bid_with_bid_strategies() {
+    let (mut deps, env) = init();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let update_bid_strategy = |bid_strategy: BidStrategy| ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: Some(bid_strategy),
+        exit_fee: None,
+        surplus_policy: None,
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+
+    // a ladder without steps should fail
+    let owner = mock_info("owner", &[]);
+    let res = apply_config_change(
+        deps.as_mut().branch(),
+        env.clone(),
+        owner.clone(),
+        update_bid_strategy(BidStrategy::Ladder {
+            step: Uint128::zero(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::InvalidBidStrategy {
+            reason: "ladder step must be greater than zero".to_string()
+        }
+    );
+
+    // the minimum allowed bid is 20_051 and the maximum allowed bid is capped by the bidding
+    // balance, as 95% of the basket value is higher
+    for (bid_strategy, amount) in [
+        (
+            BidStrategy::JumpBid {
+                fraction: Decimal::percent(80),
+            },
+            "24000",
+        ),
+        (
+            BidStrategy::JumpBid {
+                fraction: Decimal::percent(10),
+            },
+            "20051",
+        ),
+        (
+            BidStrategy::Ladder {
+                step: Uint128::from(1_000u128),
+            },
+            "21000",
+        ),
+        (BidStrategy::MaxAllowed, "30000"),
+    ] {
+        apply_config_change(
+            deps.as_mut().branch(),
+            env.clone(),
+            owner.clone(),
+            update_bid_strategy(bid_strategy.clone()),
+        )
+        .unwrap();
+
+        let info = mock_info("bot", &[]);
+        let msg = ExecuteMsg::TryBid {
+            auction_round: 1,
+            basket_value: Some(Uint128::from(100_000u128)),
+            max_bid: None,
+            expected_highest_bid: None,
+            slippage: None,
+        };
+        let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "try_bid"),
+                attr("amount", amount),
+                attr("bid_strategy", bid_strategy.to_string())
+            ]
+        );
+    }
 }
 
-// TODO: to test settle auction, need to comment the line that checks if the auction round is valid
-// on executions.rs
-//
-// use crate::state::{TREASURE_CHEST_CONTRACTS, UNSETTLED_AUCTION};
-// #[test]
-// fn settle_auction_as_loser_works() {
-//     let (mut deps, mut env) = init();
-
-//     // join pool with one user & enough funds to be able to outbid default highest bid (20000)
-//     let info = mock_info("robinho", &coins(30_000, "native_denom"));
-//     let msg = ExecuteMsg::JoinPool {
-//         auction_round: 1,
-//         basket_value: Uint128::from(25_000u128),
-//     };
-//     let _ = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
-
-//     // mock the auction round to be 0 so the contract thinks the auction round is over
-//     let mut unsettled_auction = UNSETTLED_AUCTION.load(deps.as_ref().storage).unwrap();
-//     unsettled_auction.auction_round = 0;
-//     UNSETTLED_AUCTION.save(deps.as_mut().storage, &unsettled_auction).unwrap();
-//     env.block.time = env.block.time.plus_days(7);
-
-//     // settle auction with contract not being the highest bidder should work
-//     let info = mock_info("bot", &[]);
-//     let msg = ExecuteMsg::SettleAuction {
-//         auction_round: 1,
-//         auction_winner: "highest_bidder".to_string(),
-//         auction_winning_bid: Uint128::from(20000u128),
-//     };
-//     let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap();
-//     assert_eq!(res.messages.len(), 0);
-//     assert_eq!(
-//         res.attributes,
-//         vec![
-//             attr("action", "settle_auction"),
-//             attr("settled_auction_round", "0"),
-//             attr("new_auction_round", "1"),
-//         ]
-//     );
-
-//     let unsettled_auction = UNSETTLED_AUCTION.load(deps.as_ref().storage).unwrap();
-//     assert_eq!(unsettled_auction.auction_round, 1);
-//     assert_eq!(unsettled_auction.basket, vec![coin(10_000, "uatom")]);
-//     assert_eq!(unsettled_auction.closing_time, 1_571_797_419 + 7 * 86_400);
-//     assert_eq!(unsettled_auction.lp_subdenom, 0);
-
-//     // funds should be released
-//     assert!(!FUNDS_LOCKED.load(deps.as_ref().storage).unwrap());
-// }
-
-// #[test]
-// fn settle_auction_as_winner_works() {
-//     let (mut deps, mut env) = init();
-
-//     // join pool with one user & enough funds to be able to outbid default highest bid (20000)
-//     let info = mock_info("robinho", &coins(30_000, "native_denom"));
-//     let msg = ExecuteMsg::JoinPool {
-//         auction_round: 1,
-//         basket_value: Uint128::from(25_000u128),
-//     };
-//     let _ = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
-
-//     // mock the auction round to be 0 so the contract thinks the auction round is over
-//     let mut unsettled_auction = UNSETTLED_AUCTION.load(deps.as_ref().storage).unwrap();
-//     unsettled_auction.auction_round = 0;
-//     unsettled_auction.lp_subdenom = 1;
-//     UNSETTLED_AUCTION.save(deps.as_mut().storage, &unsettled_auction).unwrap();
-//     env.block.time = env.block.time.plus_days(7);
-
-//     // settle auction with highest bidder should work
-//     let info = mock_info("bot", &[]);
-//     let msg = ExecuteMsg::SettleAuction {
-//         auction_round: 1,
-//         auction_winner: "cosmos2contract".to_string(),
-//         auction_winning_bid: Uint128::from(20000u128),
-//     };
-//     let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
-
-//     // check the stargate settle auction message is correct
-//     assert_eq!(
-//         res.messages[0].msg,
-//         CosmosMsg::Bank(BankMsg::Send {
-//             to_address: "rewards_addr".to_string(),
-//             // 10% of the basket assets (10_000 uatom)
-//             amount: coins(10_000 * 10 / 100, "uatom"),
-//         })
-//     );
-
-//     assert_eq!(
-//         res.messages[1].msg,
-//         WasmMsg::Instantiate2 {
-//             admin: Some("cosmos2contract".to_string()),
-//             code_id: 1,
-//             label: "Treasure chest for auction round 0".to_string(),
-//             msg: to_json_binary(&treasurechest::chest::InstantiateMsg {
-//                 denom: "native_denom".to_string(),
-//                 owner: "cosmos2contract".to_string(),
-//                 notes: "factory/cosmos2contract/auction.1".to_string(),
-//                 token_factory: TokenFactoryType::Injective.to_string(),
-//                 burn_it: Some(false)
-//             })
-//             .unwrap(),
-//             funds: vec![coin(10_000, "native_denom"), coin(10_000 * 90 / 100, "uatom")],
-//             salt: Binary::from(
-//                 format!(
-//                     "{}{}{}",
-//                     unsettled_auction.auction_round,
-//                     "bot".to_string(),
-//                     env.block.height
-//                 )
-//                 .as_bytes()
-//             ),
-//         }
-//         .into()
-//     );
-
-//     let treasure_chest_addr = TREASURE_CHEST_CONTRACTS.load(&deps.storage, 0).unwrap();
-
-//     assert_eq!(
-//         res.messages[2].msg,
-//         TokenFactoryType::Injective.change_admin(
-//             Addr::unchecked("cosmos2contract"),
-//             "factory/cosmos2contract/auction.1",
-//             treasure_chest_addr
-//         )
-//     );
-
-//     assert_eq!(
-//         res.messages[3].msg,
-//         TokenFactoryType::Injective.create_denom(Addr::unchecked(MOCK_CONTRACT_ADDR),
-// "auction.2")     );
-
-//     assert_eq!(
-//         res.attributes,
-//         vec![
-//             attr("action", "settle_auction"),
-//             attr("settled_auction_round", "0"),
-//             attr("new_auction_round", "1"),
-//             attr(
-//                 "treasure_chest_address",
-//                 // this is a mock address, as the checksum was invented
-//                 "ED9963158CC851609F6BCFE30C3256F3471F11E3087F6DB5244B1FE5659757C4"
-//             ),
-//             attr("new_subdenom", "auction.2"),
-//         ]
-//     );
-
-//     // funds should be released
-//     assert!(!FUNDS_LOCKED.load(deps.as_ref().storage).unwrap());
-// }
+#[test]
+fn try_bid_with_caller_limits() {
+    let (mut deps, env) = init();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let try_bid = |max_bid: Option<u128>,
+                   expected_highest_bid: Option<u128>,
+                   slippage: Option<Decimal>| ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: max_bid.map(Uint128::from),
+        expected_highest_bid: expected_highest_bid.map(Uint128::from),
+        slippage,
+    };
+    let info = mock_info("bot", &[]);
+
+    // a max bid below the minimum allowed bid should not bid
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        info.clone(),
+        try_bid(Some(20_000), None, None),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "did_not_bid"),
+            attr("reason", "minimum_allowed_bid_is_higher_than_max_bid")
+        ]
+    );
+
+    // a highest bid above the expected one should not bid
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        info.clone(),
+        try_bid(None, Some(19_000), None),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "did_not_bid"), attr("reason", "highest_bid_moved_beyond_slippage")]
+    );
+    assert!(!FUNDS_LOCKED.load(&deps.storage).unwrap());
+
+    // a highest bid within the slippage should bid, capped by the max bid
+    let owner = mock_info("owner", &[]);
+    let msg = ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: Some(BidStrategy::MaxAllowed),
+        exit_fee: None,
+        surplus_policy: None,
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+    apply_config_change(deps.as_mut().branch(), env.clone(), owner, msg).unwrap();
+
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        info,
+        try_bid(Some(25_000), Some(19_000), Some(Decimal::percent(10))),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "try_bid"),
+            attr("amount", "25000"),
+            attr("bid_strategy", "max_allowed")
+        ]
+    );
+}
+
+#[test]
+fn try_bid_within_bid_window() {
+    let (mut deps, mut env) = init();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: Some(3_600),
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: None,
+        settlement_quorum: None,
+        min_deposit: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let closes_at = deps.querier.current_auction.auction_closing_time.i64() as u64;
+    let msg = QueryMsg::BidWindow {};
+    let res: BidWindowResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        BidWindowResponse {
+            bid_window: 3_600,
+            opens_at: closes_at - 3_600,
+            closes_at,
+            is_open: false,
+        }
+    );
+
+    // bidding before the window opens should not bid
+    let info = mock_info("bot", &[]);
+    let try_bid = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), try_bid.clone()).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "did_not_bid"), attr("reason", "outside_bid_window")]
+    );
+    assert!(!FUNDS_LOCKED.load(&deps.storage).unwrap());
+
+    // bidding within the window should work
+    env.block.time = Timestamp::from_seconds(closes_at - 60);
+    let res: BidWindowResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert!(res.is_open);
+
+    let res = execute(deps.as_mut().branch(), env.clone(), info, try_bid).unwrap();
+    assert_eq!(res.attributes[0], attr("action", "try_bid"));
+    assert!(FUNDS_LOCKED.load(&deps.storage).unwrap());
+}
+
+#[test]
+fn try_bid_fails() {
+    let (mut deps, env) = init();
+
+    // join pool with one user & enough funds to be able to outbid default highest bid (20000)
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    let _ = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // try bid from non-whitelisted address should fail
+    let info = mock_info("non_whitelisted", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(10_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg);
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+    // try_bid with wrong auction round should fail
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 2,
+        basket_value: Some(Uint128::from(10_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::InvalidAuctionRound {
+            current_auction_round: 1,
+            auction_round: 2
+        }
+    );
+
+    // try_bid with funds should fail
+    let info = mock_info("bot", &coins(20_000, "native_denom"));
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(10_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(res, ContractError::PaymentError(cw_utils::PaymentError::NonPayable {}));
+}
+
+#[test]
+fn try_bid_with_on_chain_valuation() {
+    let (mut deps, mut env) = init();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // try bid without basket value and no price sources should fail
+    let info = mock_info("anyone", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: None,
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(res, ContractError::BasketValueRequired);
+
+    // configure the oracle to price uatom at 10 native_denom
+    let owner = mock_info("owner", &[]);
+    let update_pricing = |price_sources: Vec<DenomPriceSource>| ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: Some(PricingConfig {
+            price_sources,
+            max_price_age: 60,
+            basket_value_tolerance: Decimal::percent(5),
+            price_feed: None,
+        }),
+        bid_strategy: None,
+        exit_fee: None,
+        surplus_policy: None,
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+    let oracle_source = DenomPriceSource {
+        denom: "uatom".to_string(),
+        source: PriceSource::Oracle {
+            oracle_type: 2,
+            base: "ATOM".to_string(),
+            quote: "INJ".to_string(),
+            base_decimals: 6,
+            quote_decimals: 18,
+        },
+    };
+    let res = apply_config_change(
+        deps.as_mut().branch(),
+        env.clone(),
+        owner.clone(),
+        update_pricing(vec![oracle_source.clone(), oracle_source.clone()]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::DuplicatePriceSource {
+            denom: "uatom".to_string()
+        }
+    );
+    apply_config_change(
+        deps.as_mut().branch(),
+        env.clone(),
+        owner.clone(),
+        update_pricing(vec![oracle_source]),
+    )
+    .unwrap();
+
+    // no price from the oracle should fail
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::PriceUnavailable {
+            denom: "uatom".to_string()
+        }
+    );
+
+    let updated_at = env.block.time.seconds() as i64;
+    deps.querier.oracle_price = Some(PricePairState {
+        pair_price: "10".to_string(),
+        base_price: "100".to_string(),
+        quote_price: "10".to_string(),
+        base_cumulative_price: "0".to_string(),
+        quote_cumulative_price: "0".to_string(),
+        base_timestamp: updated_at,
+        quote_timestamp: updated_at,
+    });
+
+    // a basket value too far from the on-chain value should fail
+    let bot = mock_info("bot", &[]);
+    let bot_msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(200_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), bot, bot_msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::BasketValueMismatch {
+            supplied: Uint128::from(200_000u128),
+            on_chain: Uint128::from(100_000u128),
+        }
+    );
+
+    // stale prices should fail
+    let mut late_env = env.clone();
+    late_env.block.time = env.block.time.plus_seconds(61);
+    let res = execute(deps.as_mut().branch(), late_env, info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::StalePrice {
+            denom: "uatom".to_string(),
+            updated_at: updated_at as u64,
+        }
+    );
+
+    // anyone can bid once the basket is valued on-chain
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "try_bid"),
+            attr("amount", "20051"),
+            attr("bid_strategy", "minimal_increment")
+        ]
+    );
+
+    // the spot market mid price can only cross-check a bidder's basket value
+    apply_config_change(
+        deps.as_mut().branch(),
+        env.clone(),
+        owner,
+        update_pricing(vec![DenomPriceSource {
+            denom: "uatom".to_string(),
+            source: PriceSource::SpotMarket {
+                market_id: "0x01".to_string(),
+            },
+        }]),
+    )
+    .unwrap();
+    deps.querier.spot_mid_price = "1.5".to_string();
+    env.block.height += 1;
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SpotPriceRequiresBasketValue {
+            denom: "uatom".to_string(),
+        }
+    );
+
+    // a spot price far from the supplied value is rejected
+    let bot_msg = |basket_value: u128| ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(basket_value)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), bot_msg(100_000))
+        .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::BasketValueMismatch {
+            supplied: Uint128::from(100_000u128),
+            on_chain: Uint128::from(15_000u128),
+        }
+    );
+
+    // 10_000 uatom * 1.5 is not worth bidding for
+    let res = execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), bot_msg(15_000))
+        .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "did_not_bid"),
+            attr("reason", "basket_value_is_not_worth_bidding_for")
+        ]
+    );
+}
+
+#[test]
+fn try_bid_with_price_feed() {
+    let (mut deps, env) = init();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let price_source = DenomPriceSource {
+        denom: "uatom".to_string(),
+        source: PriceSource::PriceFeed {
+            feed_id: "atom".to_string(),
+            decimals: 6,
+        },
+    };
+    let mut pricing = PricingConfig {
+        price_sources: vec![price_source],
+        max_price_age: 60,
+        basket_value_tolerance: Decimal::percent(5),
+        price_feed: None,
+    };
+    let owner = mock_info("owner", &[]);
+    let update_pricing = |pricing: PricingConfig| ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: Some(pricing),
+        bid_strategy: None,
+        exit_fee: None,
+        surplus_policy: None,
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+
+    // price feed sources without a price feed contract should fail
+    let res = apply_config_change(
+        deps.as_mut().branch(),
+        env.clone(),
+        owner.clone(),
+        update_pricing(pricing.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::InvalidPriceFeed {
+            feed_id: "atom".to_string(),
+            reason: "no price feed contract configured".to_string(),
+        }
+    );
+
+    pricing.price_feed = Some(PriceFeedConfig {
+        contract: Addr::unchecked("pyth"),
+        native_feed_id: "inj".to_string(),
+        native_decimals: 12,
+        max_confidence: Decimal::percent(1),
+    });
+
+    // price feed sources are always checked for staleness
+    let res = apply_config_change(
+        deps.as_mut().branch(),
+        env.clone(),
+        owner.clone(),
+        update_pricing(PricingConfig {
+            max_price_age: 0,
+            ..pricing.clone()
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::MissingMaxPriceAge {
+            denom: "uatom".to_string(),
+        }
+    );
+
+    apply_config_change(deps.as_mut().branch(), env.clone(), owner, update_pricing(pricing))
+        .unwrap();
+
+    let info = mock_info("anyone", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: None,
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+
+    // missing feeds should fail
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert!(matches!(res, ContractError::InvalidPriceFeed { feed_id, .. } if feed_id == "atom"));
+
+    // ATOM at $10 and INJ at $25
+    let now = env.block.time.seconds() as i64;
+    let feed_price = |price: i64, conf: u64, publish_time: i64| FeedPrice {
+        price: Int64::new(price),
+        conf: Uint64::new(conf),
+        expo: -8,
+        publish_time,
+    };
+    deps.querier.price_feeds.insert("atom".to_string(), feed_price(1_000_000_000, 100, now));
+    deps.querier.price_feeds.insert("inj".to_string(), feed_price(2_500_000_000, 100, now - 61));
+
+    // stale feeds should fail
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::InvalidPriceFeed {
+            feed_id: "inj".to_string(),
+            reason: format!("price is stale, published at {}", now - 61),
+        }
+    );
+
+    // feeds with a wide confidence interval should fail
+    deps.querier.price_feeds.insert("inj".to_string(), feed_price(2_500_000_000, 100_000_000, now));
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::InvalidPriceFeed {
+            feed_id: "inj".to_string(),
+            reason: "confidence interval is too wide".to_string(),
+        }
+    );
+
+    // 10_000 uatom are worth 10_000 * 0.4 * 10^6 native_denom, so the contract bids
+    deps.querier.price_feeds.insert("inj".to_string(), feed_price(2_500_000_000, 100, now));
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "try_bid"),
+            attr("amount", "20051"),
+            attr("bid_strategy", "minimal_increment")
+        ]
+    );
+}
+
+#[test]
+fn settle_auction_as_loser_works() {
+    let (mut deps, env) = init();
+
+    // join pool with one user & enough funds to be able to outbid default highest bid (20000)
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // settling the auction before the round is over should fail
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(res, ContractError::AuctionRoundHasNotFinished);
+
+    // settle auction with contract not being the highest bidder should work
+    deps.querier.start_round(2);
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "settle_auction"),
+            attr("settled_auction_round", "1"),
+            attr("new_auction_round", "2"),
+        ]
+    );
+
+    // funds should be released and kept for the next round
+    assert!(!FUNDS_LOCKED.load(deps.as_ref().storage).unwrap());
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(30_000u128));
+
+    let msg = QueryMsg::Round {
+        round: 1,
+    };
+    let res: RoundRecord = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        RoundRecord {
+            auction_round: 1,
+            basket: vec![coin(10_000, "uatom")],
+            winner: "highest_bidder".to_string(),
+            winning_bid: Uint128::from(25_000u128),
+            won: false,
+            bids: vec![RoundBid {
+                amount: Uint128::from(20_051u128),
+                height: env.block.height,
+                time: env.block.time.seconds(),
+            }],
+            fees: vec![],
+            lp_subdenom: 0,
+            treasure_chest: None,
+            settled_at: env.block.time.seconds(),
+        }
+    );
+}
+
+#[test]
+fn pending_exits_processed_at_settlement() {
+    let (mut deps, env) = init();
+
+    for (user, amount) in [("robinho", 30_000u128), ("ronaldinho", 10_000)] {
+        let info = mock_info(user, &coins(amount, "native_denom"));
+        let msg = ExecuteMsg::JoinPool {
+            auction_round: 1,
+            rounds: None,
+        };
+        execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    }
+
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // requesting an exit while the pool is locked escrows the LP tokens
+    let lp_denom = format!("factory/{}/auction.0", env.contract.address);
+    let info = mock_info("robinho", &coins(5_000, lp_denom.as_str()));
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::RequestExit {}).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "request_exit"), attr("amount", "5000"), attr("exit_fee_shares", "0")]
+    );
+
+    let msg = QueryMsg::PendingExit {
+        address: "robinho".to_string(),
+    };
+    let res: Option<PendingExit> =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        Some(PendingExit {
+            auction_round: 1,
+            lp_subdenom: 0,
+            amount: Uint128::from(5_000u128),
+            fee_shares: Uint128::zero(),
+        })
+    );
+
+    // cancelling the exit returns the escrowed LP tokens
+    let info = mock_info("ronaldinho", &coins(10_000, lp_denom.as_str()));
+    execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::RequestExit {}).unwrap();
+    let info = mock_info("ronaldinho", &[]);
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), ExecuteMsg::CancelExit {})
+        .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: "ronaldinho".to_string(),
+            amount: coins(10_000, lp_denom.as_str()),
+        }
+        .into()
+    );
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::CancelExit {}).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::NoPendingExit {
+            address: "ronaldinho".to_string()
+        }
+    );
+
+    // settling a lost auction refunds the pending exits
+    deps.querier.start_round(2);
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|msg| msg.msg).collect::<Vec<_>>(),
+        vec![
+            TokenFactoryType::Injective.burn(
+                env.contract.address.clone(),
+                lp_denom.as_str(),
+                Uint128::from(5_000u128),
+            ),
+            BankMsg::Send {
+                to_address: "robinho".to_string(),
+                amount: coins(5_000, "native_denom"),
+            }
+            .into(),
+        ]
+    );
+    assert_eq!(res.attributes.last().unwrap(), attr("pending_exits_processed", "1"));
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(35_000u128));
+
+    let msg = QueryMsg::PendingExits {
+        start_after: None,
+        limit: None,
+    };
+    let res: PendingExitsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert!(res.exits.is_empty());
+}
+
+#[test]
+fn pending_exits_charged_exit_fee() {
+    let (mut deps, env) = init();
+
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: None,
+        exit_fee: Some(ExitFeeSchedule {
+            base_fee: Decimal::percent(1),
+            max_fee: Decimal::percent(1),
+            ramp_seconds: 0,
+        }),
+        surplus_policy: None,
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+    apply_config_change(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    for (user, amount) in [("robinho", 30_000u128), ("ronaldinho", 10_000)] {
+        let info = mock_info(user, &coins(amount, "native_denom"));
+        let msg = ExecuteMsg::JoinPool {
+            auction_round: 1,
+            rounds: None,
+        };
+        execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    }
+
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // the exit fee is charged on the LP tokens escrowed, at the rate of the request time
+    let lp_denom = format!("factory/{}/auction.0", env.contract.address);
+    let info = mock_info("robinho", &coins(10_000, lp_denom.as_str()));
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::RequestExit {}).unwrap();
+    assert_eq!(res.attributes.last().unwrap(), attr("exit_fee_shares", "100"));
+
+    // the refund of a lost auction keeps the fee in the bidding balance, like exiting right away
+    deps.querier.start_round(2);
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|msg| msg.msg).collect::<Vec<_>>(),
+        vec![
+            TokenFactoryType::Injective.burn(
+                env.contract.address.clone(),
+                lp_denom.as_str(),
+                Uint128::from(10_000u128),
+            ),
+            BankMsg::Send {
+                to_address: "robinho".to_string(),
+                amount: coins(9_900, "native_denom"),
+            }
+            .into(),
+        ]
+    );
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(30_100u128));
+
+    let res: NavResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::Nav {}).unwrap()).unwrap();
+    assert_eq!(res.lp_supply, Uint128::from(30_000u128));
+}
+
+#[test]
+fn pending_deposits_join_next_round() {
+    let (mut deps, env) = init();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let join_pool = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, join_pool.clone()).unwrap();
+
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // joining after the contract bid keeps the deposit pending for the next round
+    let info = mock_info("ronaldinho", &coins(10_000, "native_denom"));
+    let res = execute(deps.as_mut().branch(), env.clone(), info, join_pool.clone()).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(res.attributes.last().unwrap(), attr("pending_auction_round", "2"));
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(30_000u128));
+
+    let msg = QueryMsg::PendingDeposits {
+        address: "ronaldinho".to_string(),
+    };
+    let res: PendingDepositsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res.deposits,
+        vec![PendingDeposit {
+            auction_round: 2,
+            amount: Uint128::from(10_000u128),
+        }]
+    );
+
+    // pending deposits can be withdrawn
+    let info = mock_info("ronaldinho", &[]);
+    let cancel_deposit = ExecuteMsg::CancelDeposit {
+        auction_round: 2,
+    };
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), info.clone(), cancel_deposit.clone()).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        BankMsg::Send {
+            to_address: "ronaldinho".to_string(),
+            amount: coins(10_000, "native_denom"),
+        }
+        .into()
+    );
+    let res = execute(deps.as_mut().branch(), env.clone(), info, cancel_deposit).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::NoPendingDeposit {
+            address: "ronaldinho".to_string(),
+            auction_round: 2,
+        }
+    );
+
+    let info = mock_info("ronaldinho", &coins(10_000, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, join_pool).unwrap();
+
+    // settling the auction mints the LP tokens of the pending deposits
+    deps.querier.start_round(2);
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    let lp_denom = format!("factory/{}/auction.0", env.contract.address);
+    assert_eq!(
+        res.messages.into_iter().map(|msg| msg.msg).collect::<Vec<_>>(),
+        vec![
+            TokenFactoryType::Injective.mint(
+                env.contract.address.clone(),
+                lp_denom.as_str(),
+                Uint128::from(10_000u128),
+            ),
+            BankMsg::Send {
+                to_address: "ronaldinho".to_string(),
+                amount: coins(10_000, lp_denom.as_str()),
+            }
+            .into(),
+        ]
+    );
+    assert_eq!(res.attributes.last().unwrap(), attr("pending_deposits_activated", "1"));
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(40_000u128));
+
+    let msg = QueryMsg::UserPosition {
+        address: "ronaldinho".to_string(),
+    };
+    let res: UserPositionResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.active_amount, Uint128::from(10_000u128));
+}
+
+#[test]
+fn deposits_for_future_rounds() {
+    let (mut deps, env) = init();
+
+    // deposits for a later round are kept out of the bidding balance
+    for (user, auction_round, amount) in
+        [("robinho", 2u64, 10_000u128), ("ronaldinho", 2, 5_000), ("robinho", 3, 7_000)]
+    {
+        let info = mock_info(user, &coins(amount, "native_denom"));
+        let msg = ExecuteMsg::JoinPool {
+            auction_round,
+            rounds: None,
+        };
+        let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(
+            res.attributes.last().unwrap(),
+            attr("pending_auction_round", auction_round.to_string())
+        );
+    }
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::zero());
+
+    // pending deposits cannot be committed
+    let info = mock_info("robinho", &coins(1_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 2,
+        rounds: Some(2),
+    };
+    let err = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidCommitment { .. }));
+
+    let totals = QueryMsg::PendingDepositTotals {
+        start_after: None,
+        limit: None,
+    };
+    let res: PendingDepositTotalsResponse =
+        from_json(query(deps.as_ref(), env.clone(), totals.clone()).unwrap()).unwrap();
+    assert_eq!(res.totals, vec![(2, Uint128::from(15_000u128)), (3, Uint128::from(7_000u128))]);
+
+    // cancelling a deposit updates the totals of the round
+    let info = mock_info("ronaldinho", &[]);
+    let msg = ExecuteMsg::CancelDeposit {
+        auction_round: 2,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    let res: PendingDepositTotalsResponse =
+        from_json(query(deps.as_ref(), env.clone(), totals.clone()).unwrap()).unwrap();
+    assert_eq!(res.totals, vec![(2, Uint128::from(10_000u128)), (3, Uint128::from(7_000u128))]);
+
+    // only the deposits of the round starting are activated
+    deps.querier.start_round(2);
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.attributes.last().unwrap(), attr("pending_deposits_activated", "1"));
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(10_000u128));
+
+    let res: PendingDepositTotalsResponse =
+        from_json(query(deps.as_ref(), env.clone(), totals).unwrap()).unwrap();
+    assert_eq!(res.totals, vec![(3, Uint128::from(7_000u128))]);
+}
+
+#[test]
+fn settle_auction_as_winner_works() {
+    let (mut deps, env) = init();
+
+    // join pool with one user & enough funds to be able to outbid default highest bid (20000)
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let msg = QueryMsg::Stats {};
+    let res: StatsResponse = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.stats.rounds_participated, 1);
+    assert_eq!(res.stats.depositor_count, 1);
+
+    // settle auction with the contract being the highest bidder should work
+    deps.querier.start_round(2);
+
+    // the contract received the basket, on top of the unused balance and the funds sent to settle
+    deps.querier.bank.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(10_000, "uatom"), coin(9_951, "native_denom")],
+    );
+    let info = mock_info("bot", &coins(2, "native_denom"));
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: env.contract.address.to_string(),
+        auction_winning_bid: Uint128::from(20_051u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // 10% of the basket assets go to the rewards fee address
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "rewards_addr".to_string(),
+            amount: coins(1_000, "uatom"),
+        })
+    );
+
+    let treasure_chest_addr = TREASURE_CHEST_CONTRACTS.load(&deps.storage, 1).unwrap();
+    assert_eq!(
+        res.messages[2].msg,
+        TokenFactoryType::Injective.change_admin(
+            env.contract.address.clone(),
+            format!("factory/{}/auction.0", env.contract.address).as_str(),
+            treasure_chest_addr.clone(),
+        )
+    );
+    assert_eq!(
+        res.messages[3].msg,
+        TokenFactoryType::Injective.create_denom(env.contract.address.clone(), "auction.1")
+    );
+
+    // the bidding balance is reset as the unused balance goes to the treasure chest
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::zero());
+    assert!(!FUNDS_LOCKED.load(deps.as_ref().storage).unwrap());
+
+    let msg = QueryMsg::RoundHistory {
+        start_after: None,
+        limit: None,
+    };
+    let res: RoundHistoryResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.rounds.len(), 1);
+    let record = &res.rounds[0];
+    assert!(record.won);
+    assert_eq!(record.winning_bid, Uint128::from(20_051u128));
+    assert_eq!(record.bids.len(), 1);
+    assert_eq!(record.fees, coins(1_000, "uatom"));
+    assert_eq!(record.lp_subdenom, 0);
+    assert_eq!(record.treasure_chest, Some(treasure_chest_addr));
+
+    let unsettled_auction = UNSETTLED_AUCTION.load(&deps.storage).unwrap();
+    assert_eq!(unsettled_auction.auction_round, 2);
+    assert_eq!(unsettled_auction.lp_subdenom, 1);
+
+    let msg = QueryMsg::Stats {};
+    let res: StatsResponse = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res.stats,
+        PoolStats {
+            rounds_participated: 1,
+            rounds_won: 1,
+            total_bid: Uint128::from(20_051u128),
+            total_burned: Uint128::from(20_051u128),
+            total_fees: coins(1_000, "uatom"),
+            total_distributed: vec![coin(9_000, "uatom"), coin(9_949, "native_denom")],
+            depositor_count: 0,
+        }
+    );
+}
+
+#[test]
+fn pool_stats_track_losses_wins_and_rollovers() {
+    let (mut deps, env) = init();
+    let bot = mock_info("bot", &[]);
+    let stats = |deps: &OwnedDeps<MemoryStorage, AuctionApi, AuctionQuerier>| {
+        let res: StatsResponse =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Stats {}).unwrap()).unwrap();
+        res.stats
+    };
+    let bid = |auction_round: u64| ExecuteMsg::TryBid {
+        auction_round,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    let settle = |auction_round: u64, auction_winner: &str| ExecuteMsg::SettleAuction {
+        auction_round,
+        auction_winner: auction_winner.to_string(),
+        auction_winning_bid: Uint128::from(20_051u128),
+    };
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // a lost round only counts the bid
+    execute(deps.as_mut().branch(), env.clone(), bot.clone(), bid(1)).unwrap();
+    deps.querier.start_round(2);
+    execute(deps.as_mut().branch(), env.clone(), bot.clone(), settle(1, "highest_bidder")).unwrap();
+    assert_eq!(
+        stats(&deps),
+        PoolStats {
+            rounds_participated: 1,
+            rounds_won: 0,
+            total_bid: Uint128::from(20_051u128),
+            total_burned: Uint128::zero(),
+            total_fees: vec![],
+            total_distributed: vec![],
+            depositor_count: 1,
+        }
+    );
+
+    // a won round distributes the basket and the native surplus sent to the treasure chest
+    execute(deps.as_mut().branch(), env.clone(), bot.clone(), bid(2)).unwrap();
+    deps.querier.start_round(3);
+    deps.querier.bank.update_balance(MOCK_CONTRACT_ADDR, coins(10_000, "uatom"));
+    let info = mock_info("bot", &coins(2, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, settle(2, MOCK_CONTRACT_ADDR)).unwrap();
+    assert_eq!(
+        stats(&deps),
+        PoolStats {
+            rounds_participated: 2,
+            rounds_won: 1,
+            total_bid: Uint128::from(40_102u128),
+            total_burned: Uint128::from(20_051u128),
+            total_fees: coins(1_000, "uatom"),
+            total_distributed: vec![coin(9_000, "uatom"), coin(9_949, "native_denom")],
+            depositor_count: 0,
+        }
+    );
+
+    // a rolled over surplus stays in the pool, so it isn't distributed
+    let msg = ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: None,
+        exit_fee: None,
+        surplus_policy: Some(SurplusPolicy::RollOver),
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+    apply_config_change(deps.as_mut().branch(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let info = mock_info("ronaldinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 3,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    execute(deps.as_mut().branch(), env.clone(), bot.clone(), bid(3)).unwrap();
+    deps.querier.start_round(4);
+    let info = mock_info("bot", &coins(2, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, settle(3, MOCK_CONTRACT_ADDR)).unwrap();
+    assert_eq!(
+        stats(&deps),
+        PoolStats {
+            rounds_participated: 3,
+            rounds_won: 2,
+            total_bid: Uint128::from(60_153u128),
+            total_burned: Uint128::from(40_102u128),
+            total_fees: coins(2_000, "uatom"),
+            total_distributed: vec![coin(18_000, "uatom"), coin(9_949, "native_denom")],
+            depositor_count: 0,
+        }
+    );
+}
+
+#[test]
+fn settle_auction_as_winner_uses_received_basket() {
+    let (mut deps, env) = init();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // denoms joined the basket since the round started, and the snapshot follows them on the bid
+    deps.querier.current_auction.amount =
+        vec![coin(200, "native_denom"), coin(10_000, "uatom"), coin(400, "uusdt")];
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        UNSETTLED_AUCTION.load(&deps.storage).unwrap().basket,
+        vec![coin(10_000, "uatom"), coin(200, "native_denom"), coin(400, "uusdt")]
+    );
+
+    // a keeper bond and a deposit for a future round are owed by the contract
+    let info = mock_info("keeper", &coins(1_000, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::BondKeeper {}).unwrap();
+    let info = mock_info("ronaldinho", &coins(5_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 3,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // the contract already bid on the next round, which the auction module holds in escrow
+    deps.querier.start_round(2);
+    deps.querier.current_auction.highest_bidder = env.contract.address.to_string();
+    deps.querier.current_auction.highest_bid_amount = Uint128::from(1_000u128);
+
+    // the basket grew during the round, while the escrowed LP tokens and the denom joining the
+    // basket after the bid are not part of it
+    let lp_denom = format!("factory/{}/auction.0", env.contract.address);
+    deps.querier.bank.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![
+            coin(1_000, lp_denom.as_str()),
+            coin(9_949 - 1_000 + 1_000 + 5_000 + 2 + 300, "native_denom"),
+            coin(12_000, "uatom"),
+            coin(500, "uusdt"),
+            coin(700, "ujuno"),
+        ],
+    );
+
+    let info = mock_info("bot", &coins(2, "native_denom"));
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: env.contract.address.to_string(),
+        auction_winning_bid: Uint128::from(20_051u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // the fees are taken from what the contract actually received
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "rewards_addr".to_string(),
+            amount: vec![coin(1_200, "uatom"), coin(30, "native_denom"), coin(50, "uusdt")],
+        })
+    );
+    assert!(res
+        .attributes
+        .contains(&attr("snapshot_basket", "10000uatom,200native_denom,400uusdt")));
+    assert!(res
+        .attributes
+        .contains(&attr("received_basket", "12000uatom,300native_denom,500uusdt")));
+
+    let msg = QueryMsg::Round {
+        round: 1,
+    };
+    let res: RoundRecord = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res.basket,
+        vec![coin(12_000, "uatom"), coin(300, "native_denom"), coin(500, "uusdt")]
+    );
+    assert_eq!(res.fees, vec![coin(1_200, "uatom"), coin(30, "native_denom"), coin(50, "uusdt")]);
+
+    // the owner sweeps the denom the contract doesn't account for, but not the ones it owes
+    let sweep = |denom: &str| ExecuteMsg::SweepUntrackedFunds {
+        denom: denom.to_string(),
+        recipient: "rewards_addr".to_string(),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), sweep("ujuno"))
+        .unwrap_err();
+    assert_eq!(res, ContractError::Ownership(cw_ownable::OwnershipError::NotOwner));
+
+    for denom in ["native_denom", "uatom", lp_denom.as_str()] {
+        let res =
+            execute(deps.as_mut().branch(), env.clone(), mock_info("owner", &[]), sweep(denom))
+                .unwrap_err();
+        assert_eq!(
+            res,
+            ContractError::TrackedDenom {
+                denom: denom.to_string(),
+            }
+        );
+    }
+
+    let res =
+        execute(deps.as_mut().branch(), env, mock_info("owner", &[]), sweep("ujuno")).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "rewards_addr".to_string(),
+            amount: coins(700, "ujuno"),
+        })
+    );
+}
+
+#[test]
+fn settle_auction_as_winner_rolls_over_surplus() {
+    let (mut deps, env) = init();
+
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: None,
+        exit_fee: None,
+        surplus_policy: Some(SurplusPolicy::RollOver),
+        treasury_chest_code_id: None,
+        config_timelock: None,
+    };
+    apply_config_change(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    deps.querier.start_round(2);
+    deps.querier.bank.update_balance(MOCK_CONTRACT_ADDR, coins(10_000, "uatom"));
+    let info = mock_info("bot", &coins(2, "native_denom"));
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: env.contract.address.to_string(),
+        auction_winning_bid: Uint128::from(20_051u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // the new LP tokens matching the unused balance are minted before funding the treasure chest
+    let new_lp_denom = format!("factory/{}/auction.1", env.contract.address);
+    assert_eq!(
+        res.messages[0].msg,
+        TokenFactoryType::Injective.create_denom(env.contract.address.clone(), "auction.1")
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        TokenFactoryType::Injective.mint(
+            env.contract.address.clone(),
+            new_lp_denom.as_str(),
+            Uint128::from(9_949u128),
+        )
+    );
+    match &res.messages[3].msg {
+        CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+            funds,
+            ..
+        }) => assert_eq!(funds, &vec![coin(9_949, new_lp_denom.as_str()), coin(9_000, "uatom")]),
+        msg => panic!("unexpected message {msg:?}"),
+    }
+    assert_eq!(res.messages.len(), 5);
+    assert_eq!(res.attributes.last().unwrap(), attr("rolled_over", "9949"));
+
+    // the unused balance stays in the pool for the next round
+    let res: NavResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::Nav {}).unwrap()).unwrap();
+    assert_eq!(res.nav, Uint128::from(9_949u128));
+    assert_eq!(res.lp_supply, Uint128::from(9_949u128));
+}
+
+#[test]
+fn commitments_renewed_at_settlement() {
+    let (mut deps, env) = init();
+
+    // committing to zero rounds is not allowed
+    let info = mock_info("robinho", &coins(20_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: Some(0),
+    };
+    let err = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidCommitment { .. }));
+
+    // the LP tokens of the committed deposit are escrowed by the contract
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: Some(2),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.attributes.last().unwrap(), attr("last_committed_round", "2"));
+
+    let info = mock_info("ronaldinho", &coins(10_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let msg = QueryMsg::Commitments {
+        start_after: None,
+        limit: None,
+    };
+    let res: CommitmentsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        res.commitments,
+        vec![(
+            Addr::unchecked("robinho"),
+            Commitment {
+                lp_subdenom: 0,
+                amount: Uint128::from(20_000u128),
+                last_auction_round: 2,
+            }
+        )]
+    );
+
+    let info = mock_info("bot", &[]);
+    let msg_bid = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg_bid).unwrap();
+
+    deps.querier.start_round(2);
+    deps.querier.bank.update_balance(MOCK_CONTRACT_ADDR, coins(10_000, "uatom"));
+    let info = mock_info("bot", &coins(2, "native_denom"));
+    let settle = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: env.contract.address.to_string(),
+        auction_winning_bid: Uint128::from(20_051u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, settle).unwrap();
+
+    // the committed share of the unused balance re-enters the pool, the committed share of the
+    // basket is sent out and the rest goes to the treasure chest
+    let old_lp_denom = format!("factory/{}/auction.0", env.contract.address);
+    let new_lp_denom = format!("factory/{}/auction.1", env.contract.address);
+    assert_eq!(
+        res.messages[0].msg,
+        TokenFactoryType::Injective.create_denom(env.contract.address.clone(), "auction.1")
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        TokenFactoryType::Injective.mint(
+            env.contract.address.clone(),
+            new_lp_denom.as_str(),
+            Uint128::from(6_632u128),
+        )
+    );
+    assert_eq!(
+        res.messages[2].msg,
+        TokenFactoryType::Injective.burn(
+            env.contract.address.clone(),
+            old_lp_denom.as_str(),
+            Uint128::from(20_000u128),
+        )
+    );
+    assert_eq!(
+        res.messages.last().unwrap().msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "robinho".to_string(),
+            amount: coins(6_000, "uatom"),
+        })
+    );
+    match &res.messages[4].msg {
+        CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+            funds,
+            ..
+        }) => assert_eq!(funds, &vec![coin(3_317, "native_denom"), coin(3_000, "uatom")]),
+        msg => panic!("unexpected message {msg:?}"),
+    }
+    assert!(res.attributes.contains(&attr("commitments_reentered", "6632")));
+
+    let res: NavResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::Nav {}).unwrap()).unwrap();
+    assert_eq!(res.nav, Uint128::from(6_632u128));
+    assert_eq!(res.lp_supply, Uint128::from(6_632u128));
+
+    let res: CommitmentsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        res.commitments,
+        vec![(
+            Addr::unchecked("robinho"),
+            Commitment {
+                lp_subdenom: 1,
+                amount: Uint128::from(6_632u128),
+                last_auction_round: 2,
+            }
+        )]
+    );
+
+    // the commitment expires once its last round is settled, releasing the LP tokens
+    deps.querier.start_round(3);
+    let info = mock_info("bot", &[]);
+    let settle = ExecuteMsg::SettleAuction {
+        auction_round: 2,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, settle).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "robinho".to_string(),
+            amount: coins(6_632, new_lp_denom.as_str()),
+        })
+    );
+    assert!(res.attributes.contains(&attr("commitments_expired", "1")));
+
+    let res: CommitmentsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert!(res.commitments.is_empty());
+}
+
+#[test]
+fn settlement_queue_processed_in_batches() {
+    let (mut deps, env) = init();
+
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: None,
+        settlement_quorum: None,
+        min_deposit: Some(Uint128::from(100u128)),
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // dust deposits kept pending are refused
+    let join_pool = ExecuteMsg::JoinPool {
+        auction_round: 2,
+        rounds: None,
+    };
+    let info = mock_info("dust", &coins(99, "native_denom"));
+    let err = execute(deps.as_mut().branch(), env.clone(), info, join_pool.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BelowMinDeposit {
+            amount: Uint128::from(99u128),
+            min_deposit: Uint128::from(100u128),
+        }
+    );
+
+    for i in 0..32 {
+        let info = mock_info(&format!("depositor{i:02}"), &coins(100, "native_denom"));
+        execute(deps.as_mut().branch(), env.clone(), info, join_pool.clone()).unwrap();
+    }
+
+    // the deposits join the pool at once, but only the first batch gets its LP tokens
+    deps.querier.start_round(2);
+    let settle = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), settle).unwrap();
+    assert_eq!(res.messages.len(), 31);
+    assert!(res.attributes.contains(&attr("pending_deposits_activated", "30")));
+    assert_eq!(res.attributes.last().unwrap(), attr("settlement_queue_pending", "1"));
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(3_200u128));
+
+    let res: Option<SettlementQueue> =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::SettlementQueue {}).unwrap())
+            .unwrap();
+    let queue = res.unwrap();
+    assert_eq!(queue.deposits, Uint128::from(200u128));
+    assert_eq!(queue.deposit_shares, Uint128::from(200u128));
+
+    // the next auction round can't be settled before the queue is processed
+    deps.querier.start_round(3);
+    let settle = ExecuteMsg::SettleAuction {
+        auction_round: 2,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let err = execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), settle.clone())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SettlementQueuePending {
+            auction_round: 1
+        }
+    );
+
+    // anyone can process the rest of the queue
+    let process = ExecuteMsg::ProcessSettlementQueue {
+        limit: None,
+    };
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), mock_info("anyone", &[]), process.clone())
+            .unwrap();
+    let lp_denom = format!("factory/{}/auction.0", env.contract.address);
+    assert_eq!(
+        res.messages.into_iter().map(|msg| msg.msg).collect::<Vec<_>>(),
+        vec![
+            BankMsg::Send {
+                to_address: "depositor30".to_string(),
+                amount: coins(100, lp_denom.as_str()),
+            }
+            .into(),
+            BankMsg::Send {
+                to_address: "depositor31".to_string(),
+                amount: coins(100, lp_denom.as_str()),
+            }
+            .into(),
+        ]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "process_settlement_queue"), attr("pending_deposits_activated", "2")]
+    );
+    assert!(!SETTLEMENT_QUEUE.exists(&deps.storage));
+
+    let err = execute(deps.as_mut().branch(), env.clone(), mock_info("anyone", &[]), process)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NoSettlementQueue);
+
+    execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), settle).unwrap();
+}
+
+#[test]
+fn keeper_bond_and_slashing() {
+    let (mut deps, mut env) = init();
+    let now = env.block.time.seconds();
+
+    let info = mock_info("owner", &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        keeper_min_bond: Some(Uint128::from(1_000u128)),
+        keeper_unbonding_period: Some(86_400),
+        settlement_challenge_period: None,
+        settlement_quorum: None,
+        min_deposit: None,
+    };
+
+    // the bond must stay slashable for at least an auction period
+    let res = execute(deps.as_mut().branch(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::KeeperUnbondingPeriodTooShort {
+            min_unbonding_period: 604_800,
+        }
+    );
+
+    let msg = ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        keeper_min_bond: Some(Uint128::from(1_000u128)),
+        keeper_unbonding_period: Some(604_800),
+        settlement_challenge_period: None,
+        settlement_quorum: None,
+        min_deposit: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // settling without a bond should fail
+    deps.querier.start_round(2);
+    let info = mock_info("bot", &[]);
+    let settle = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), info, settle.clone()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::InsufficientKeeperBond {
+            min_bond: Uint128::from(1_000u128),
+        }
+    );
+
+    // bonding enough should allow to settle, keeping the report
+    let info = mock_info("bot", &coins(1_000, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::BondKeeper {}).unwrap();
+
+    let info = mock_info("bot", &[]);
+    execute(deps.as_mut().branch(), env.clone(), info, settle).unwrap();
+
+    let msg = QueryMsg::SettlementReport {
+        auction_round: 1,
+    };
+    let res: Option<SettlementReport> =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        Some(SettlementReport {
+            keeper: Addr::unchecked("bot"),
+            auction_winner: "highest_bidder".to_string(),
+            auction_winning_bid: Uint128::from(25_000u128),
+            reported_at: now,
+            slashed: false,
+            dispute: None,
+            reporters: vec![],
+        })
+    );
+
+    // a report matching the auction result cannot be slashed
+    let info = mock_info("anyone", &[]);
+    let slash = ExecuteMsg::SlashKeeper {
+        auction_round: 1,
+    };
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), info.clone(), slash.clone()).unwrap_err();
+    assert_eq!(res, ContractError::EmptyAuctionResult {});
+
+    deps.querier.last_auction_result = Some(LastAuctionResult {
+        winner: "highest_bidder".to_string(),
+        amount: "25000".to_string(),
+        round: 1,
+    });
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), info.clone(), slash.clone()).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::ReportMatchesAuctionResult {
+            auction_round: 1,
+        }
+    );
+
+    // a wrong report slashes the whole bond to the depositors
+    deps.querier.last_auction_result = Some(LastAuctionResult {
+        winner: "another_bidder".to_string(),
+        amount: "26000".to_string(),
+        round: 1,
+    });
+    execute(deps.as_mut().branch(), env.clone(), info.clone(), slash.clone()).unwrap();
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(31_000u128));
+
+    let res = execute(deps.as_mut().branch(), env.clone(), info, slash).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::ReportAlreadySlashed {
+            auction_round: 1,
+        }
+    );
+
+    let msg = QueryMsg::Slashings {
+        start_after: None,
+        limit: None,
+    };
+    let res: SlashingsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res.slashings,
+        vec![Slashing {
+            auction_round: 1,
+            keeper: Addr::unchecked("bot"),
+            reporters: vec![],
+            amount: Uint128::from(1_000u128),
+            reported_winner: "highest_bidder".to_string(),
+            reported_winning_bid: Uint128::from(25_000u128),
+            auction_winner: "another_bidder".to_string(),
+            auction_winning_bid: Uint128::from(26_000u128),
+            slashed_at: now,
+        }]
+    );
+
+    let msg = QueryMsg::KeeperBond {
+        address: "bot".to_string(),
+    };
+    let res: Option<KeeperBond> =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res, None);
+
+    // unbonded amounts can only be claimed after the unbonding period
+    let info = mock_info("keeper", &coins(2_000, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::BondKeeper {}).unwrap();
+
+    let info = mock_info("keeper", &[]);
+    let msg = ExecuteMsg::UnbondKeeper {
+        amount: Uint128::from(500u128),
+    };
+    execute(deps.as_mut().branch(), env.clone(), info.clone(), msg).unwrap();
+
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), info.clone(), ExecuteMsg::ClaimKeeperBond {})
+            .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::KeeperBondNotReleased {
+            release_at: now + 604_800,
+        }
+    );
+
+    env.block.time = Timestamp::from_seconds(now + 604_800);
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::ClaimKeeperBond {}).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "keeper".to_string(),
+            amount: coins(500, "native_denom"),
+        })
+    );
+
+    let msg = QueryMsg::KeeperBonds {
+        start_after: None,
+        limit: None,
+    };
+    let res: KeeperBondsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res.bonds,
+        vec![(
+            Addr::unchecked("keeper"),
+            KeeperBond {
+                amount: Uint128::from(1_500u128),
+                unbonding: Uint128::zero(),
+                unbonding_release_at: None,
+                bonded_at: now,
+            }
+        )]
+    );
+}
+
+/// Settles auction round 1 as won by the contract with the report of a keeper bonding 1_000, which
+/// can be slashed afterwards
+fn won_round_with_slashable_keeper() -> (OwnedDeps<MemoryStorage, AuctionApi, AuctionQuerier>, Env)
+{
+    let (mut deps, mut env) = init();
+    let now = env.block.time.seconds();
+
+    let owner = mock_info("owner", &[]);
+    let update_config = |keeper_unbonding_period: u64| ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        keeper_min_bond: Some(Uint128::from(1_000u128)),
+        keeper_unbonding_period: Some(keeper_unbonding_period),
+        settlement_challenge_period: Some(3_600),
+        settlement_quorum: None,
+        min_deposit: None,
+    };
+
+    // the bond must stay slashable during the challenge period and an auction period
+    let res = execute(deps.as_mut().branch(), env.clone(), owner.clone(), update_config(604_800))
+        .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::KeeperUnbondingPeriodTooShort {
+            min_unbonding_period: 608_400,
+        }
+    );
+    execute(deps.as_mut().branch(), env.clone(), owner, update_config(608_400)).unwrap();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    let info = mock_info("bot", &coins(1_000, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::BondKeeper {}).unwrap();
+
+    let bot = mock_info("bot", &[]);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 1,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), bot.clone(), msg).unwrap();
+
+    // the keeper reports the contract won, then unbonds its whole bond
+    deps.querier.start_round(2);
+    deps.querier.bank.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(10_000, "uatom"), coin(10_951, "native_denom")],
+    );
+    let lp_denom = format!("factory/{}/auction.0", env.contract.address);
+    deps.querier.bank.update_balance("robinho", coins(30_000, lp_denom.as_str()));
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: env.contract.address.to_string(),
+        auction_winning_bid: Uint128::from(20_051u128),
+    };
+    execute(deps.as_mut().branch(), env.clone(), bot.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::UnbondKeeper {
+        amount: Uint128::from(1_000u128),
+    };
+    execute(deps.as_mut().branch(), env.clone(), bot.clone(), msg).unwrap();
+
+    // the unbonding can't be claimed while the report has not settled the auction
+    env.block.time = Timestamp::from_seconds(now + 608_400);
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), bot.clone(), ExecuteMsg::ClaimKeeperBond {})
+            .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::KeeperBondFrozen {
+            auction_round: 1,
+        }
+    );
+
+    let info = mock_info("bot", &coins(2, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::FinalizeSettlement {}).unwrap();
+
+    // the auction module shows the keeper reported the wrong winner
+    deps.querier.last_auction_result = Some(LastAuctionResult {
+        winner: "another_bidder".to_string(),
+        amount: "26000".to_string(),
+        round: 1,
+    });
+
+    (deps, env)
+}
+
+#[test]
+fn keeper_bond_frozen_and_slashed_to_treasure_chest() {
+    let (mut deps, env) = won_round_with_slashable_keeper();
+
+    // the depositors of the round redeem their LP tokens from the treasure chest, so the bond
+    // slashed is added to its rewards instead of the bidding balance of the next round
+    let msg = ExecuteMsg::SlashKeeper {
+        auction_round: 1,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+    let treasure_chest_addr = TREASURE_CHEST_CONTRACTS.load(&deps.storage, 1).unwrap();
+    assert_eq!(
+        res.messages.into_iter().map(|msg| msg.msg).collect::<Vec<_>>(),
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: treasure_chest_addr.to_string(),
+            msg: to_json_binary(&treasurechest::chest::ExecuteMsg::AddRewards {}).unwrap(),
+            funds: coins(1_000, "native_denom"),
+        })]
+    );
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::zero());
+
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        mock_info("bot", &[]),
+        ExecuteMsg::ClaimKeeperBond {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::NoKeeperBond {
+            address: "bot".to_string(),
+        }
+    );
+}
+
+#[test]
+fn keeper_slashed_to_bidding_balance_with_outdated_treasure_chest() {
+    let (mut deps, env) = won_round_with_slashable_keeper();
+
+    // the treasure chest code is upgraded, so the chest of the round may not accept rewards
+    let msg = ExecuteMsg::ProposeConfig {
+        rewards_fee: None,
+        rewards_fee_addr: None,
+        min_return: None,
+        pricing: None,
+        bid_strategy: None,
+        exit_fee: None,
+        surplus_policy: None,
+        treasury_chest_code_id: Some(2),
+        config_timelock: None,
+    };
+    let res =
+        apply_config_change(deps.as_mut().branch(), env.clone(), mock_info("owner", &[]), msg)
+            .unwrap();
+    assert!(res.attributes.contains(&attr("treasury_chest_code_id", "2")));
+
+    let msg = ExecuteMsg::SlashKeeper {
+        auction_round: 1,
+    };
+    let res = execute(deps.as_mut().branch(), env, mock_info("anyone", &[]), msg).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(1_000u128));
+}
+
+#[test]
+fn optimistic_settlement_with_dispute_window() {
+    let (mut deps, mut env) = init();
+    let now = env.block.time.seconds();
+
+    let owner = mock_info("owner", &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: Some(3_600),
+        settlement_quorum: None,
+        min_deposit: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), owner.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::GrantRole {
+        role: Role::Settler,
+        address: "keeper".to_string(),
+        expires_at: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), owner.clone(), msg).unwrap();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // the settlement is kept as a pending claim during the challenge period
+    deps.querier.start_round(2);
+    let bot = mock_info("bot", &[]);
+    let settle = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), bot.clone(), settle.clone()).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "settle_auction"),
+            attr("pending_auction_round", "1"),
+            attr("finalizes_at", (now + 3_600).to_string()),
+        ]
+    );
+    assert_eq!(UNSETTLED_AUCTION.load(&deps.storage).unwrap().auction_round, 1);
+
+    let res = execute(deps.as_mut().branch(), env.clone(), bot.clone(), settle).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SettlementPending {
+            auction_round: 1,
+        }
+    );
+
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        bot.clone(),
+        ExecuteMsg::FinalizeSettlement {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SettlementChallengePeriodActive {
+            finalizes_at: now + 3_600,
+        }
+    );
+
+    // another keeper disputes the settlement with contradicting data
+    let info = mock_info("keeper", &coins(500, "native_denom"));
+    execute(deps.as_mut().branch(), env.clone(), info, ExecuteMsg::BondKeeper {}).unwrap();
+    let keeper = mock_info("keeper", &[]);
+    let dispute = |auction_winner: &str| ExecuteMsg::DisputeSettlement {
+        auction_winner: auction_winner.to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), keeper.clone(), dispute("highest_bidder"))
+            .unwrap_err();
+    assert_eq!(res, ContractError::DisputeMatchesSettlement {});
+
+    execute(deps.as_mut().branch(), env.clone(), keeper.clone(), dispute("another_bidder"))
+        .unwrap();
+
+    let res: Option<PendingSettlement> =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::PendingSettlement {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        res,
+        Some(PendingSettlement {
+            auction_round: 1,
+            keeper: Addr::unchecked("bot"),
+            auction_winner: "highest_bidder".to_string(),
+            auction_winning_bid: Uint128::from(25_000u128),
+            reported_at: now,
+            finalizes_at: now + 3_600,
+            dispute: Some(SettlementDispute {
+                keeper: Addr::unchecked("keeper"),
+                auction_winner: "another_bidder".to_string(),
+                auction_winning_bid: Uint128::from(25_000u128),
+                disputed_at: now,
+            }),
+        })
+    );
+
+    // a disputed settlement can't be finalized, only resolved by the owner
+    env.block.time = Timestamp::from_seconds(now + 3_600);
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        bot.clone(),
+        ExecuteMsg::FinalizeSettlement {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SettlementDisputed {
+            auction_round: 1,
+        }
+    );
+
+    // missed auction rounds can't be caught up while the dispute is pending
+    deps.querier.start_round(3);
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), bot.clone(), ExecuteMsg::CatchUpSettlement {})
+            .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SettlementDisputed {
+            auction_round: 1,
+        }
+    );
+    deps.querier.start_round(2);
+
+    let resolve = ExecuteMsg::ResolveSettlementDispute {
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), keeper, resolve.clone()).unwrap_err();
+    assert_eq!(res, ContractError::Ownership(cw_ownable::OwnershipError::NotOwner));
+
+    let res = execute(deps.as_mut().branch(), env.clone(), owner, resolve).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "resolve_settlement_dispute"),
+            attr("auction_round", "1"),
+            attr("settled_auction_round", "1"),
+            attr("new_auction_round", "2"),
+        ]
+    );
+    assert_eq!(UNSETTLED_AUCTION.load(&deps.storage).unwrap().auction_round, 2);
+
+    // the report was right, so the keeper that disputed it is slashed
+    deps.querier.last_auction_result = Some(LastAuctionResult {
+        winner: "highest_bidder".to_string(),
+        amount: "25000".to_string(),
+        round: 1,
+    });
+    let msg = ExecuteMsg::SlashKeeper {
+        auction_round: 1,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+    assert!(res.attributes.contains(&attr("keeper", "keeper")));
+    assert!(res.attributes.contains(&attr("amount", "500")));
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(30_500u128));
+
+    // the auction module confirms the pending settlement before the challenge period ends
+    deps.querier.start_round(3);
+    let settle = |auction_round: u64| ExecuteMsg::SettleAuction {
+        auction_round,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    execute(deps.as_mut().branch(), env.clone(), bot.clone(), settle(2)).unwrap();
+
+    deps.querier.last_auction_result = Some(LastAuctionResult {
+        winner: "highest_bidder".to_string(),
+        amount: "25000".to_string(),
+        round: 2,
+    });
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), bot.clone(), ExecuteMsg::TrySettleAuction {})
+            .unwrap();
+    assert!(res.attributes.contains(&attr("pending_settlement", "confirmed")));
+    assert_eq!(UNSETTLED_AUCTION.load(&deps.storage).unwrap().auction_round, 3);
+
+    // an undisputed settlement is finalized once the challenge period has passed
+    deps.querier.start_round(4);
+    execute(deps.as_mut().branch(), env.clone(), bot.clone(), settle(3)).unwrap();
+
+    env.block.time = Timestamp::from_seconds(now + 7_200);
+    let res = execute(deps.as_mut().branch(), env.clone(), bot, ExecuteMsg::FinalizeSettlement {})
+        .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "finalize_settlement"),
+            attr("auction_round", "3"),
+            attr("settled_auction_round", "3"),
+            attr("new_auction_round", "4"),
+        ]
+    );
+
+    let res: Option<PendingSettlement> =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::PendingSettlement {}).unwrap())
+            .unwrap();
+    assert_eq!(res, None);
+}
+
+#[test]
+fn settle_auction_with_keeper_quorum() {
+    let (mut deps, env) = init();
+    let now = env.block.time.seconds();
+
+    let owner = mock_info("owner", &[]);
+    let msg = ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: None,
+        settlement_quorum: Some(2),
+        min_deposit: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), owner.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateWhiteListedAddresses {
+        remove: vec![],
+        add: vec!["keeper".to_string()],
+    };
+    execute(deps.as_mut().branch(), env.clone(), owner, msg).unwrap();
+
+    deps.querier.start_round(2);
+    let settle = |auction_winner: &str| ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: auction_winner.to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+
+    // a single report is not enough to settle the auction
+    let bot = mock_info("bot", &[]);
+    let res = execute(deps.as_mut().branch(), env.clone(), bot.clone(), settle("highest_bidder"))
+        .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "settle_auction"),
+            attr("reported_auction_round", "1"),
+            attr("matching_reports", "1"),
+            attr("settlement_quorum", "2"),
+        ]
+    );
+
+    // reporting the same result twice doesn't count twice
+    execute(deps.as_mut().branch(), env.clone(), bot, settle("highest_bidder")).unwrap();
+    assert_eq!(UNSETTLED_AUCTION.load(&deps.storage).unwrap().auction_round, 1);
+
+    // a contradicting report doesn't count towards the quorum
+    let keeper = mock_info("keeper", &[]);
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), keeper.clone(), settle("another_bidder"))
+            .unwrap();
+    assert!(res.attributes.contains(&attr("matching_reports", "1")));
+
+    let msg = QueryMsg::KeeperReports {
+        auction_round: 1,
+        start_after: None,
+        limit: None,
+    };
+    let res: KeeperReportsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        res.reports,
+        vec![
+            (
+                Addr::unchecked("bot"),
+                KeeperReport {
+                    auction_winner: "highest_bidder".to_string(),
+                    auction_winning_bid: Uint128::from(25_000u128),
+                    reported_at: now,
+                }
+            ),
+            (
+                Addr::unchecked("keeper"),
+                KeeperReport {
+                    auction_winner: "another_bidder".to_string(),
+                    auction_winning_bid: Uint128::from(25_000u128),
+                    reported_at: now,
+                }
+            ),
+        ]
+    );
+
+    // the report of a keeper no longer holding the settler role doesn't count
+    let owner = mock_info("owner", &[]);
+    let msg_whitelist =
+        |remove: Vec<String>, add: Vec<String>| ExecuteMsg::UpdateWhiteListedAddresses {
+            remove,
+            add,
+        };
+    execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        owner.clone(),
+        msg_whitelist(vec!["bot".to_string()], vec![]),
+    )
+    .unwrap();
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), keeper.clone(), settle("highest_bidder"))
+            .unwrap();
+    assert!(res.attributes.contains(&attr("matching_reports", "1")));
+
+    // the auction is settled once the keepers agree
+    execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        owner,
+        msg_whitelist(vec![], vec!["bot".to_string()]),
+    )
+    .unwrap();
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), keeper, settle("highest_bidder")).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "settle_auction"),
+            attr("settled_auction_round", "1"),
+            attr("new_auction_round", "2"),
+        ]
+    );
+
+    let res: KeeperReportsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert!(res.reports.is_empty());
+
+    // every keeper whose report counted is recorded, so that they can all be slashed
+    let msg = QueryMsg::SettlementReport {
+        auction_round: 1,
+    };
+    let res: Option<SettlementReport> =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    let report = res.unwrap();
+    assert_eq!(report.keeper, Addr::unchecked("keeper"));
+    assert_eq!(report.reporters, vec![Addr::unchecked("bot")]);
+
+    deps.querier.last_auction_result = Some(LastAuctionResult {
+        winner: "another_bidder".to_string(),
+        amount: "25000".to_string(),
+        round: 1,
+    });
+    let msg = ExecuteMsg::SlashKeeper {
+        auction_round: 1,
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+    assert!(res.attributes.contains(&attr("keeper", "keeper")));
+    assert!(res.attributes.contains(&attr("reporter", "bot")));
+}
+
+#[test]
+fn catch_up_settlement_for_missed_rounds() {
+    let (mut deps, env) = init();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        min_next_bid_increment_rate: None,
+        bid_window: None,
+        lock_period_seconds: None,
+        rebid_buffer: None,
+        keeper_min_bond: None,
+        keeper_unbonding_period: None,
+        settlement_challenge_period: None,
+        settlement_quorum: Some(2),
+        min_deposit: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // nothing to catch up while only the previous round is unsettled
+    deps.querier.start_round(2);
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 1,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), msg).unwrap();
+
+    let anyone = mock_info("anyone", &[]);
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        anyone.clone(),
+        ExecuteMsg::CatchUpSettlement {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::NoMissedAuctionRounds {
+            unsettled: 1,
+        }
+    );
+
+    // the contract bids on round 3 while round 1 is still unsettled
+    deps.querier.start_round(3);
+    let msg = ExecuteMsg::TryBid {
+        auction_round: 3,
+        basket_value: Some(Uint128::from(100_000u128)),
+        max_bid: None,
+        expected_highest_bid: None,
+        slippage: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), msg).unwrap();
+
+    // the rounds never bid on are settled as lost, until the round the contract bid on
+    deps.querier.start_round(5);
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        anyone.clone(),
+        ExecuteMsg::CatchUpSettlement {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "catch_up_settlement"),
+            attr("missed_rounds_settled", "2"),
+            attr("attestation_required", "3"),
+        ]
+    );
+    assert_eq!(UNSETTLED_AUCTION.load(&deps.storage).unwrap().auction_round, 3);
+
+    // the reports on the missed rounds are cleared along with them
+    let msg = QueryMsg::KeeperReports {
+        auction_round: 1,
+        start_after: None,
+        limit: None,
+    };
+    let res: KeeperReportsResponse =
+        from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert!(res.reports.is_empty());
+
+    let msg = QueryMsg::Round {
+        round: 1,
+    };
+    let res: RoundRecord = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        RoundRecord {
+            auction_round: 1,
+            basket: vec![coin(10_000, "uatom")],
+            winner: String::new(),
+            winning_bid: Uint128::zero(),
+            won: false,
+            bids: vec![],
+            fees: vec![],
+            lp_subdenom: 0,
+            treasure_chest: None,
+            settled_at: env.block.time.seconds(),
+        }
+    );
+
+    // running it again is safe, the attestation is still required
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        anyone.clone(),
+        ExecuteMsg::CatchUpSettlement {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::AttestationRequired {
+            auction_round: 3,
+        }
+    );
+
+    // neither can the keepers settle it, nor the auction module
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 3,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+    };
+    let res = execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), msg).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::AttestationRequired {
+            auction_round: 3,
+        }
+    );
+
+    deps.querier.last_auction_result = Some(LastAuctionResult {
+        winner: "highest_bidder".to_string(),
+        amount: "25000".to_string(),
+        round: 3,
+    });
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        anyone.clone(),
+        ExecuteMsg::TrySettleAuction {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::AttestationRequired {
+            auction_round: 3,
+        }
+    );
+
+    let res: Option<u64> =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::AttestationRequired {}).unwrap())
+            .unwrap();
+    assert_eq!(res, Some(3));
+
+    // only the owner can attest the result of the round the contract bid on
+    let attest = ExecuteMsg::AttestAuctionRound {
+        auction_round: 3,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+        basket: None,
+    };
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), anyone.clone(), attest.clone()).unwrap_err();
+    assert_eq!(res, ContractError::Ownership(cw_ownable::OwnershipError::NotOwner));
+
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), mock_info("owner", &[]), attest).unwrap();
+    assert!(res.attributes.contains(&attr("settled_auction_round", "3")));
+
+    // round 4 is still to be caught up, with an unknown basket and the closing time of the current
+    // auction round
+    let unsettled_auction = UNSETTLED_AUCTION.load(&deps.storage).unwrap();
+    assert_eq!(unsettled_auction.auction_round, 4);
+    assert!(unsettled_auction.basket.is_empty());
+    assert_eq!(
+        unsettled_auction.closing_time,
+        deps.querier.current_auction.auction_closing_time.i64() as u64
+    );
+    assert!(!FUNDS_LOCKED.load(&deps.storage).unwrap());
+
+    // the last missed round is settled with the result of the auction module
+    deps.querier.start_round(6);
+    deps.querier.last_auction_result = Some(LastAuctionResult {
+        winner: "another_bidder".to_string(),
+        amount: "30000".to_string(),
+        round: 5,
+    });
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        anyone.clone(),
+        ExecuteMsg::CatchUpSettlement {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "catch_up_settlement"),
+            attr("missed_rounds_settled", "1"),
+            attr("settled_auction_round", "5"),
+            attr("new_auction_round", "6"),
+        ]
+    );
+
+    let msg = QueryMsg::Round {
+        round: 5,
+    };
+    let res: RoundRecord = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.winner, "another_bidder".to_string());
+    assert_eq!(res.winning_bid, Uint128::from(30_000u128));
+    assert_eq!(BIDDING_BALANCE.load(&deps.storage).unwrap(), Uint128::from(30_000u128));
+
+    let res =
+        execute(deps.as_mut().branch(), env.clone(), anyone, ExecuteMsg::CatchUpSettlement {})
+            .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::NoMissedAuctionRounds {
+            unsettled: 6,
+        }
+    );
+}
+
+#[test]
+fn attest_consecutive_missed_rounds() {
+    let (mut deps, env) = init();
+
+    let info = mock_info("robinho", &coins(30_000, "native_denom"));
+    let msg = ExecuteMsg::JoinPool {
+        auction_round: 1,
+        rounds: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), info, msg).unwrap();
+
+    // the contract bids on rounds 2 and 3 while round 1 is still unsettled
+    for auction_round in [2, 3] {
+        deps.querier.start_round(auction_round);
+        let msg = ExecuteMsg::TryBid {
+            auction_round,
+            basket_value: Some(Uint128::from(100_000u128)),
+            max_bid: None,
+            expected_highest_bid: None,
+            slippage: None,
+        };
+        execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), msg).unwrap();
+    }
+
+    deps.querier.start_round(5);
+    deps.querier.current_auction.amount = vec![coin(50_000, "uusdt")];
+    let anyone = mock_info("anyone", &[]);
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        anyone.clone(),
+        ExecuteMsg::CatchUpSettlement {},
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("attestation_required", "2")));
+
+    let owner = mock_info("owner", &[]);
+    let attest = |auction_round: u64| ExecuteMsg::AttestAuctionRound {
+        auction_round,
+        auction_winner: "highest_bidder".to_string(),
+        auction_winning_bid: Uint128::from(25_000u128),
+        basket: None,
+    };
+    execute(deps.as_mut().branch(), env.clone(), owner.clone(), attest(2)).unwrap();
+
+    // the next round the contract bid on needs an attestation as well, without the basket of the
+    // current auction round
+    let res = execute(
+        deps.as_mut().branch(),
+        env.clone(),
+        anyone.clone(),
+        ExecuteMsg::CatchUpSettlement {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "catch_up_settlement"),
+            attr("missed_rounds_settled", "0"),
+            attr("attestation_required", "3"),
+        ]
+    );
+    execute(deps.as_mut().branch(), env.clone(), owner, attest(3)).unwrap();
+
+    let msg = QueryMsg::Round {
+        round: 3,
+    };
+    let res: RoundRecord = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(res.winner, "highest_bidder".to_string());
+    assert!(res.basket.is_empty());
+    assert_eq!(res.bids.len(), 1);
+
+    let unsettled_auction = UNSETTLED_AUCTION.load(&deps.storage).unwrap();
+    assert_eq!(unsettled_auction.auction_round, 4);
+    assert!(unsettled_auction.basket.is_empty());
+    assert_eq!(
+        unsettled_auction.closing_time,
+        deps.querier.current_auction.auction_closing_time.i64() as u64
+    );
+
+    // the last missed round is settled as usual, keeping its unknown basket in the round history
+    let msg = ExecuteMsg::SettleAuction {
+        auction_round: 4,
+        auction_winner: "another_bidder".to_string(),
+        auction_winning_bid: Uint128::from(30_000u128),
+    };
+    execute(deps.as_mut().branch(), env.clone(), mock_info("bot", &[]), msg).unwrap();
+    let msg = QueryMsg::Round {
+        round: 4,
+    };
+    let res: RoundRecord = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert!(res.basket.is_empty());
+    assert_eq!(UNSETTLED_AUCTION.load(&deps.storage).unwrap().basket, vec![coin(50_000, "uusdt")]);
+}
 
 #[test]
 fn testing_math() {
@@ -13,7 +13,7 @@ use treasurechest::{
 
 use crate::executions::withdraw;
 use crate::{
-    executions::{change_token_factory, return_dust},
+    executions::{add_rewards, change_token_factory, return_dust},
     queries::{query_config, query_state},
     state::{Config, CONFIG, TOTAL_REWARDS},
 };
@@ -75,6 +75,7 @@ pub fn execute(
         ExecuteMsg::ReturnDust {
             limit,
         } => return_dust(deps, env, info.sender, limit),
+        ExecuteMsg::AddRewards {} => add_rewards(deps, env, info),
     }
 }
 
@@ -9,6 +9,7 @@ use cosmwasm_std::{Addr, BalanceResponse, BankMsg, BankQuery, Coin, CosmosMsg, D
 use cosmwasm_std::QueryRequest::Bank;
 use treasurechest::{errors::ContractError, tf::tokenfactory::TokenFactoryType};
 
+use crate::contract::split_reward_by_supply;
 use crate::state::{CONFIG, TOTAL_REWARDS};
 
 // withdraw rewards to executor
@@ -79,6 +80,26 @@ pub fn change_token_factory(
         .add_attribute("action", "treasurechest/change_token_factory"))
 }
 
+// add the funds sent to the rewards of the tickets not redeemed yet
+pub fn add_rewards(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let supply = deps.querier.query_supply(config.denom.clone())?.amount;
+    let ticket_balance = deps.querier.query_balance(env.contract.address, config.denom.clone())?;
+    let outstanding = supply.checked_sub(ticket_balance.amount)?;
+    if outstanding.is_zero() {
+        return Err(ContractError::NoTicketsOutstanding(config.denom));
+    }
+
+    for (denom, ratio) in split_reward_by_supply(info.funds, outstanding) {
+        let total = TOTAL_REWARDS.may_load(deps.storage, denom.clone())?.unwrap_or_default();
+        TOTAL_REWARDS.save(deps.storage, denom, &total.checked_add(ratio)?)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "treasurechest/add_rewards"))
+}
+
 pub fn return_dust(deps: DepsMut, env: Env, sender: Addr, limit: Option<u32>) -> Result<Response, ContractError> {
     cw_ownable::assert_owner(deps.storage, &sender)?;
     let config = CONFIG.load(deps.storage)?;
@@ -16,45 +16,188 @@ pub struct InstantiateMsg {
     pub min_next_bid_increment_rate: Decimal,
     pub treasury_chest_code_id: u64,
     pub min_return: Decimal,
+    /// Settings to value the auction basket on-chain. Defaults to no price sources
+    pub pricing: Option<PricingConfig>,
+    /// How much the contract bids on the auction. Defaults to the minimal increment
+    pub bid_strategy: Option<BidStrategy>,
+    /// Seconds before the auction closing time in which the contract can bid. Defaults to zero,
+    /// allowing bids during the whole auction round
+    pub bid_window: Option<u64>,
+    /// Seconds before the auction closing time in which users cannot exit the pool. Defaults to
+    /// zero, locking the pool only once the contract has bid
+    pub lock_period_seconds: Option<u64>,
+    /// Share of the outstanding bid kept on top of it for re-bids, when users withdraw the unbid
+    /// surplus while the pool is locked. Defaults to zero
+    pub rebid_buffer: Option<Decimal>,
+    /// Fee charged on exits, kept in the bidding balance. Defaults to no fee
+    pub exit_fee: Option<ExitFeeSchedule>,
+    /// What to do with the unused bidding balance when the auction is won. Defaults to sending it
+    /// to the treasure chest
+    pub surplus_policy: Option<SurplusPolicy>,
+    /// Seconds a proposed change of the rewards fee, the rewards fee address, the minimum return,
+    /// the pricing, the bid strategy, the exit fee, the surplus policy, the treasure chest code id
+    /// or the timelock itself waits before it can be applied. Defaults to zero
+    pub config_timelock: Option<u64>,
+    /// Minimum amount of native denom a keeper must bond to settle the auction. Defaults to zero,
+    /// not requiring a bond
+    pub keeper_min_bond: Option<Uint128>,
+    /// Seconds a keeper waits to claim the amount unbonded. Defaults to zero. When a bond is
+    /// required, it must cover the settlement challenge period and an auction period
+    pub keeper_unbonding_period: Option<u64>,
+    /// Seconds a settlement reported by a keeper can be disputed before it can be finalized.
+    /// Defaults to zero, settling the auction right away
+    pub settlement_challenge_period: Option<u64>,
+    /// Number of distinct keepers that must report the same auction result to settle the auction.
+    /// Defaults to one
+    pub settlement_quorum: Option<u32>,
+    /// Minimum amount of the deposits kept pending or committed, in native denom, and of the exit
+    /// requests, in LP tokens. Defaults to zero
+    pub min_deposit: Option<Uint128>,
 }
 
 #[cw_ownable_execute]
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
+    /// Updates the config. Only the config admins can call this. The fees, the minimum return, the
+    /// pricing, the bid strategy and the surplus policy are changed through `ProposeConfig` instead
     UpdateConfig {
+        /// Minimum next bid increment rate for the auction. Value is between 0 and 1
+        min_next_bid_increment_rate: Option<Decimal>,
+        /// Seconds before the auction closing time in which the contract can bid. Zero allows
+        /// bids during the whole auction round
+        bid_window: Option<u64>,
+        /// Seconds before the auction closing time in which users cannot exit the pool. Zero
+        /// locks the pool only once the contract has bid
+        lock_period_seconds: Option<u64>,
+        /// Share of the outstanding bid kept on top of it for re-bids, when users withdraw the
+        /// unbid surplus while the pool is locked
+        rebid_buffer: Option<Decimal>,
+        /// Minimum amount of native denom a keeper must bond to settle the auction
+        keeper_min_bond: Option<Uint128>,
+        /// Seconds a keeper waits to claim the amount unbonded. When a bond is required, it must
+        /// cover the settlement challenge period and an auction period
+        keeper_unbonding_period: Option<u64>,
+        /// Seconds a settlement reported by a keeper can be disputed before it can be finalized
+        settlement_challenge_period: Option<u64>,
+        /// Number of distinct keepers that must report the same auction result to settle the
+        /// auction
+        settlement_quorum: Option<u32>,
+        /// Minimum amount of the deposits kept pending or committed, and of the exit requests
+        min_deposit: Option<Uint128>,
+    },
+    /// Proposes a change of the config that can only be applied once the config timelock has
+    /// passed, replacing any previous proposal. Fee increases wait at least a full auction period.
+    /// Only the config admins can propose, apply or cancel config changes.
+    ProposeConfig {
         /// Percentage of the rewards that the rewards fee address will take. Value is between 0
         /// and 1
         rewards_fee: Option<Decimal>,
         /// Address to receive the rewards fee
         rewards_fee_addr: Option<String>,
-        /// Minimum next bid increment rate for the auction. Value is between 0 and 1
-        min_next_bid_increment_rate: Option<Decimal>,
         /// The minimum return allowed in percentage. 5% means the contract cannot bid for more
         /// than 95% of the basket value
         min_return: Option<Decimal>,
+        /// Settings to value the auction basket on-chain
+        pricing: Option<PricingConfig>,
+        /// How much the contract bids on the auction
+        bid_strategy: Option<BidStrategy>,
+        /// Fee charged on exits, kept in the bidding balance
+        exit_fee: Option<ExitFeeSchedule>,
+        /// What to do with the unused bidding balance when the auction is won
+        surplus_policy: Option<SurplusPolicy>,
+        /// Code id of the treasure chests instantiated for the won auction rounds
+        treasury_chest_code_id: Option<u64>,
+        /// Seconds a proposed change waits before it can be applied
+        config_timelock: Option<u64>,
     },
-    /// Updates the whitelisted addresses that can bid on or settle the auction.
+    /// Applies the proposed config change once its activation time has passed
+    ApplyConfig {},
+    /// Withdraws the proposed config change
+    CancelConfigProposal {},
+    /// Updates the whitelisted addresses that can bid on or settle the auction. Only the keeper
+    /// managers can call this.
     /// Remove is applied after add, so if an address is in both, it is removed
     UpdateWhiteListedAddresses {
         remove: Vec<String>,
         add: Vec<String>,
     },
-    /// Makes the contract bid on the auction. This is to be called by the any whitelisted address.
+    /// Grants the role to the address until the given time, or indefinitely. Admin roles are
+    /// granted by the owner, while the bidder and settler roles can also be granted by the keeper
+    /// managers
+    GrantRole {
+        role: Role,
+        address: String,
+        /// Time in seconds from which the address no longer holds the role
+        expires_at: Option<u64>,
+    },
+    /// Revokes the role from the address. Can be called by whoever can grant the role
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    /// Pauses or resumes the operations of the contract. Only the pausers can call this. Fields
+    /// left empty keep their current value
+    UpdatePauseStatus {
+        /// Whether users cannot join the pool
+        join: Option<bool>,
+        /// Whether users cannot exit the pool
+        exit: Option<bool>,
+        /// Whether the contract cannot bid on the auction
+        bid: Option<bool>,
+        /// Whether the auction cannot be settled
+        settle: Option<bool>,
+    },
+    /// Makes the contract bid on the auction. This is to be called by the any whitelisted address,
+    /// or by anyone if the basket value is left to be computed on-chain.
     TryBid {
         /// The auction round to bid on
         auction_round: u64,
-        /// The value in native denom of all assets being auctioned
-        basket_value: Uint128,
+        /// The value in native denom of all assets being auctioned. If set, it is checked against
+        /// the on-chain valuation when price sources are configured. If not set, the basket is
+        /// valued on-chain and the call is permissionless
+        basket_value: Option<Uint128>,
+        /// The maximum amount the caller approves the contract to bid
+        max_bid: Option<Uint128>,
+        /// The highest bid the caller saw when sending the message. The contract does not bid if
+        /// the highest bid moved above it by more than the slippage
+        expected_highest_bid: Option<Uint128>,
+        /// Relative increase of the highest bid over the expected highest bid the caller accepts.
+        /// Defaults to zero
+        slippage: Option<Decimal>,
     },
-    /// Called by the user to join the pooled auction .
+    /// Called by the user to join the pooled auction . LP tokens are minted at the current share
+    /// price. Deposits made once the contract has bid are kept pending, and join the pool on the
+    /// next auction round. Deposits can also be made for a later auction round, joining the pool
+    /// when that round starts.
     JoinPool {
         /// The auction round to join
         auction_round: u64,
+        /// Number of auction rounds, starting with the one joined, to keep the deposit in the pool.
+        /// The LP tokens are escrowed by the contract, and the deposit automatically re-enters the
+        /// pool with the new LP denom whenever an auction is won, until the commitment expires
+        rounds: Option<u32>,
+    },
+    /// Withdraws the pending deposit made by the user for the given auction round.
+    CancelDeposit {
+        /// The auction round the deposit is pending for
+        auction_round: u64,
     },
-    /// Can be called by the user before T-1 day from auction's end to exit the auction.
+    /// Can be called by the user to exit the auction, unless the pool is locked. The pool is
+    /// locked from `lock_period_seconds` before the auction's end, and once the contract has bid.
+    /// Once the contract has bid, users can still withdraw their share of the funds exceeding the
+    /// outstanding bid and the re-bid buffer, getting back the rest of the LP tokens sent.
     ExitPool {},
+    /// Can be called by the user while the pool is locked to escrow the LP tokens sent, exiting the
+    /// pool once the auction is settled. If the pool is not locked, the user exits right away.
+    RequestExit {},
+    /// Cancels the pending exit of the user, returning the escrowed LP tokens.
+    CancelExit {},
     /// Settles the auction, sending the rewards to the vault in case the contract won the auction.
-    /// Called by the bot.
+    /// Called by the bot. The auction is only settled once the settlement quorum of keepers have
+    /// reported the same result, which is meant for rounds that can't be settled with
+    /// TrySettleAuction anymore. With a settlement challenge period, the report is kept as a
+    /// pending settlement that is finalized once the period has passed.
     SettleAuction {
         /// The auction round to settle
         auction_round: u64,
@@ -63,7 +206,76 @@ pub enum ExecuteMsg {
         /// The amount bid by the winner of the auction
         auction_winning_bid: Uint128,
     },
+    /// Settles the latest auction round with the result of the auction module, confirming or
+    /// overriding any pending settlement. Can be called by anyone
     TrySettleAuction {},
+    /// Disputes the pending settlement with a contradicting auction result, preventing it from
+    /// being finalized until the owner or the auction module resolves it. Called by a keeper,
+    /// whose bond can be slashed if the report disputed turns out to be right
+    DisputeSettlement {
+        /// The bidder address that won the auction
+        auction_winner: String,
+        /// The amount bid by the winner of the auction
+        auction_winning_bid: Uint128,
+    },
+    /// Finalizes the pending settlement once the challenge period has passed without disputes.
+    /// Can be called by anyone
+    FinalizeSettlement {},
+    /// Resolves a disputed pending settlement, settling the auction with the given result.
+    /// Called by the owner
+    ResolveSettlementDispute {
+        /// The bidder address that won the auction
+        auction_winner: String,
+        /// The amount bid by the winner of the auction
+        auction_winning_bid: Uint128,
+    },
+    /// Settles an unsettled auction round left behind by more than one auction round, recording the
+    /// rounds missed since then as lost. Stops at the first round the contract bid on, which needs
+    /// an attestation from the owner. Can be called by anyone
+    CatchUpSettlement {},
+    /// Settles the missed auction round that needs an attestation with the given result. Called by
+    /// the owner
+    AttestAuctionRound {
+        /// The auction round to settle
+        auction_round: u64,
+        /// The bidder address that won the auction
+        auction_winner: String,
+        /// The amount bid by the winner of the auction
+        auction_winning_bid: Uint128,
+        /// The basket of the auction round, required if the contract won it and the basket is
+        /// not known
+        basket: Option<Vec<Coin>>,
+    },
+    /// Bonds the native denom sent, making the keeper eligible to settle the auction
+    BondKeeper {},
+    /// Starts unbonding the given amount of the keeper's bond, claimable once the unbonding period
+    /// has passed. The amount unbonding can still be slashed
+    UnbondKeeper {
+        amount: Uint128,
+    },
+    /// Claims the amount unbonded by the keeper once the unbonding period has passed. The bond is
+    /// frozen while a report of the keeper has not settled the auction yet
+    ClaimKeeperBond {},
+    /// Slashes the bond of the keeper that settled the given auction round, if its report disagrees
+    /// with the latest auction result, or else of the keeper that disputed the report with a wrong
+    /// result. The bond slashed goes to the depositors of the auction
+    /// round: it is added to the rewards of their treasure chest if the LP tokens of the round were
+    /// replaced by a won auction, to the bidding balance otherwise. Can be called by anyone
+    SlashKeeper {
+        auction_round: u64,
+    },
+    /// Processes the pending exits, commitments and pending deposits left in the settlement queue,
+    /// up to the given number of entries. The next auction round can only be settled once the
+    /// queue is empty. Can be called by anyone
+    ProcessSettlementQueue {
+        limit: Option<u32>,
+    },
+    /// Sends the whole balance of a denom the contract doesn't account for, such as a denom added
+    /// to the basket after the last bid of the contract, to the recipient. Called by the owner
+    SweepUntrackedFunds {
+        denom: String,
+        recipient: String,
+    },
 }
 
 #[cw_ownable_query]
@@ -72,6 +284,9 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
+    /// Returns the proposed config change, if any
+    #[returns(Option<ConfigProposal>)]
+    ConfigProposal {},
     #[returns(WhitelistedAddressesResponse)]
     WhitelistedAddresses {},
     #[returns(TreasureChestContractsResponse)]
@@ -87,6 +302,124 @@ pub enum QueryMsg {
     CurrentAuctionBasket {},
     #[returns(UnsettledAuction)]
     UnsettledAuction {},
+    /// Returns the deposits made by the given address
+    #[returns(UserPositionResponse)]
+    UserPosition {
+        address: String,
+    },
+    /// Returns the addresses that deposited on the given auction round and the amount deposited
+    #[returns(DepositorsResponse)]
+    Depositors {
+        round: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the records of the settled auction rounds
+    #[returns(RoundHistoryResponse)]
+    RoundHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the record of a settled auction round
+    #[returns(RoundRecord)]
+    Round {
+        round: u64,
+    },
+    /// Returns the lifetime statistics of the pool
+    #[returns(StatsResponse)]
+    Stats {},
+    /// Returns when the contract can bid on the current auction round
+    #[returns(BidWindowResponse)]
+    BidWindow {},
+    /// Returns when users cannot exit the pool
+    #[returns(LockStatusResponse)]
+    LockStatus {},
+    /// Returns the net asset value of the pool and the price of its LP tokens
+    #[returns(NavResponse)]
+    Nav {},
+    /// Returns the amount of native denom the given address can withdraw from the pool now
+    #[returns(WithdrawableNowResponse)]
+    WithdrawableNow {
+        address: String,
+    },
+    /// Returns the pending exit of the given address, if any
+    #[returns(Option<PendingExit>)]
+    PendingExit {
+        address: String,
+    },
+    /// Returns the deposits of the given address that will join the pool on a later auction round
+    #[returns(PendingDepositsResponse)]
+    PendingDeposits {
+        address: String,
+    },
+    /// Returns the total amount of the deposits pending for each auction round
+    #[returns(PendingDepositTotalsResponse)]
+    PendingDepositTotals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the exits that will be processed when the auction is settled
+    #[returns(PendingExitsResponse)]
+    PendingExits {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the addresses holding the given role, besides the owner and the whitelisted
+    /// addresses that implicitly hold some roles
+    #[returns(RoleHoldersResponse)]
+    RoleHolders {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the bond of the given keeper, if any
+    #[returns(Option<KeeperBond>)]
+    KeeperBond {
+        address: String,
+    },
+    /// Returns the bonds of the keepers
+    #[returns(KeeperBondsResponse)]
+    KeeperBonds {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the settlement reported by a keeper for the given auction round, if any
+    #[returns(Option<SettlementReport>)]
+    SettlementReport {
+        auction_round: u64,
+    },
+    /// Returns the results reported by the keepers for the given auction round, while the
+    /// settlement quorum has not been reached
+    #[returns(KeeperReportsResponse)]
+    KeeperReports {
+        auction_round: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the settlement waiting for the challenge period to pass, if any
+    #[returns(Option<PendingSettlement>)]
+    PendingSettlement {},
+    /// Returns the missed auction round waiting for an attestation from the owner, if any
+    #[returns(Option<u64>)]
+    AttestationRequired {},
+    /// Returns what is left to process from the last settlement, if any
+    #[returns(Option<SettlementQueue>)]
+    SettlementQueue {},
+    /// Returns the evidence of the keepers slashed, per auction round
+    #[returns(SlashingsResponse)]
+    Slashings {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the operations currently paused
+    #[returns(PauseStatus)]
+    PauseStatus {},
+    /// Returns the active multi-round commitments
+    #[returns(CommitmentsResponse)]
+    Commitments {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -114,6 +447,411 @@ pub struct FundsLockedResponse {
     pub funds_locked: bool,
 }
 
+#[cw_serde]
+pub struct RoundDeposit {
+    /// The auction round the deposit was made on
+    pub auction_round: u64,
+    /// The LP subdenom minted for the deposit
+    pub lp_subdenom: u64,
+    /// The amount of native denom deposited that has not been withdrawn yet
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct UserPositionResponse {
+    /// The deposits of the address, per auction round
+    pub deposits: Vec<RoundDeposit>,
+    /// The amount deposited that is still in the pool for the unsettled auction
+    pub active_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct DepositorsResponse {
+    pub depositors: Vec<(Addr, Uint128)>,
+}
+
+#[cw_serde]
+pub struct RoundBid {
+    /// The amount of native denom bid
+    pub amount: Uint128,
+    /// The block height at which the bid was made
+    pub height: u64,
+    /// The block time in seconds at which the bid was made
+    pub time: u64,
+}
+
+#[cw_serde]
+/// Record of a settled auction round
+pub struct RoundRecord {
+    /// The auction round number
+    pub auction_round: u64,
+    /// The coins the contract received for winning the auction round, in the denoms of the basket
+    /// snapshot. The snapshot itself, taken when the auction round started and refreshed on each
+    /// bid, when the auction round wasn't won
+    pub basket: Vec<Coin>,
+    /// The bidder address that won the auction
+    pub winner: String,
+    /// The amount bid by the winner of the auction
+    pub winning_bid: Uint128,
+    /// Whether the contract won the auction
+    pub won: bool,
+    /// The bids made by the contract on the auction round
+    pub bids: Vec<RoundBid>,
+    /// The coins sent to the rewards fee address
+    pub fees: Vec<Coin>,
+    /// The LP subdenom used for the auction round
+    pub lp_subdenom: u64,
+    /// The treasure chest contract holding the rewards, if the contract won the auction
+    pub treasure_chest: Option<Addr>,
+    /// The block time in seconds at which the auction round was settled
+    pub settled_at: u64,
+}
+
+#[cw_serde]
+pub struct RoundHistoryResponse {
+    pub rounds: Vec<RoundRecord>,
+}
+
+#[cw_serde]
+pub struct BidWindowResponse {
+    /// Seconds before the auction closing time in which the contract can bid. Zero means the
+    /// contract can bid during the whole auction round
+    pub bid_window: u64,
+    /// The time from which the contract can bid
+    pub opens_at: u64,
+    /// The time when the current auction round closes
+    pub closes_at: u64,
+    /// Whether the contract can bid now
+    pub is_open: bool,
+}
+
+#[cw_serde]
+pub struct LockStatusResponse {
+    /// Seconds before the auction closing time in which users cannot exit the pool
+    pub lock_period_seconds: u64,
+    /// The time from which users cannot exit the pool
+    pub lock_starts_at: u64,
+    /// The time from which users can exit the pool again
+    pub lock_ends_at: u64,
+    /// Whether the pool is locked because the contract has bid on the auction
+    pub bid_locked: bool,
+    /// Whether the pool is locked because the auction is about to close
+    pub time_locked: bool,
+    /// Whether users cannot exit the pool now
+    pub is_locked: bool,
+}
+
+#[cw_serde]
+/// Roles that can be granted to addresses. The owner holds every admin role, and the whitelisted
+/// addresses hold the bidder and settler roles
+pub enum Role {
+    /// Can update the config
+    ConfigAdmin,
+    /// Can grant the bidder and settler roles and update the whitelisted addresses
+    KeeperManager,
+    /// Can pause and resume the operations of the contract
+    Pauser,
+    /// Can bid on the auction with its own basket value
+    Bidder,
+    /// Can settle the auction
+    Settler,
+}
+
+impl Role {
+    /// Whether the role is granted by the owner only
+    pub fn is_admin(&self) -> bool {
+        matches!(self, Role::ConfigAdmin | Role::KeeperManager | Role::Pauser)
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::ConfigAdmin => write!(f, "config_admin"),
+            Role::KeeperManager => write!(f, "keeper_manager"),
+            Role::Pauser => write!(f, "pauser"),
+            Role::Bidder => write!(f, "bidder"),
+            Role::Settler => write!(f, "settler"),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct RoleHolder {
+    pub address: Addr,
+    /// Time in seconds from which the address no longer holds the role
+    pub expires_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct RoleHoldersResponse {
+    pub holders: Vec<RoleHolder>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+/// The native denom bonded by a keeper to settle the auction
+pub struct KeeperBond {
+    /// The amount bonded
+    pub amount: Uint128,
+    /// The amount being unbonded, still slashable until claimed
+    pub unbonding: Uint128,
+    /// The time from which the amount being unbonded can be claimed
+    pub unbonding_release_at: Option<u64>,
+    /// The time the keeper first bonded
+    pub bonded_at: u64,
+}
+
+#[cw_serde]
+pub struct KeeperBondsResponse {
+    pub bonds: Vec<(Addr, KeeperBond)>,
+}
+
+#[cw_serde]
+/// The result of an auction round as reported by the keeper that settled it
+pub struct SettlementReport {
+    /// The keeper that settled the auction round
+    pub keeper: Addr,
+    /// The winner of the auction reported
+    pub auction_winner: String,
+    /// The winning bid reported
+    pub auction_winning_bid: Uint128,
+    /// The time the auction round was settled
+    pub reported_at: u64,
+    /// Whether the keeper has been slashed for the report
+    pub slashed: bool,
+    /// The dispute raised against the report, whose keeper is slashed instead if the report matches
+    /// the auction result
+    #[serde(default)]
+    pub dispute: Option<SettlementDispute>,
+    /// The other keepers whose matching reports counted towards the settlement quorum, slashed
+    /// along with the keeper
+    #[serde(default)]
+    pub reporters: Vec<Addr>,
+}
+
+#[cw_serde]
+/// The result of an auction round reported by a keeper, counted towards the settlement quorum
+pub struct KeeperReport {
+    /// The winner of the auction reported
+    pub auction_winner: String,
+    /// The winning bid reported
+    pub auction_winning_bid: Uint128,
+    /// The time the result was reported
+    pub reported_at: u64,
+}
+
+#[cw_serde]
+pub struct KeeperReportsResponse {
+    pub reports: Vec<(Addr, KeeperReport)>,
+}
+
+#[cw_serde]
+/// A settlement reported by a keeper that can still be disputed
+pub struct PendingSettlement {
+    /// The auction round reported
+    pub auction_round: u64,
+    /// The keeper that reported the settlement
+    pub keeper: Addr,
+    /// The winner of the auction reported
+    pub auction_winner: String,
+    /// The winning bid reported
+    pub auction_winning_bid: Uint128,
+    /// The time the settlement was reported
+    pub reported_at: u64,
+    /// The time the settlement can be finalized if not disputed
+    pub finalizes_at: u64,
+    /// The dispute raised against the settlement, if any
+    pub dispute: Option<SettlementDispute>,
+}
+
+#[cw_serde]
+/// The pending exits, commitments and pending deposits of a settlement that are processed in
+/// batches. Their pool-wide effects are applied on settlement, and the amounts left are shared out
+/// pro rata as the entries are processed
+pub struct SettlementQueue {
+    /// The auction round settled
+    pub auction_round: u64,
+    /// Whether the contract won the auction round settled
+    pub won: bool,
+    /// The auction round started by the settlement
+    pub new_auction_round: u64,
+    /// The LP subdenom of the auction round started
+    pub lp_subdenom: u64,
+    /// The LP tokens of the pending exits left to refund net of the exit fees, if the auction was
+    /// lost
+    pub exit_shares: Uint128,
+    /// The native denom left to refund to the pending exits
+    pub exit_refunds: Uint128,
+    /// The LP tokens of the commitments left to renew, if the auction was won
+    pub committed: Uint128,
+    /// The new LP tokens left for the commitments renewed
+    pub reentered: Uint128,
+    /// The share of the basket left for the commitments renewed
+    pub commitment_rewards: Vec<Coin>,
+    /// The last commitment renewed
+    pub commitments_after: Option<Addr>,
+    /// The native denom of the activated deposits left to process
+    pub deposits: Uint128,
+    /// The LP tokens minted for the activated deposits left to hand out
+    pub deposit_shares: Uint128,
+}
+
+#[cw_serde]
+/// A contradicting auction result reported by another keeper
+pub struct SettlementDispute {
+    /// The keeper that disputed the settlement
+    pub keeper: Addr,
+    /// The winner of the auction according to the dispute
+    pub auction_winner: String,
+    /// The winning bid according to the dispute
+    pub auction_winning_bid: Uint128,
+    /// The time the settlement was disputed
+    pub disputed_at: u64,
+}
+
+#[cw_serde]
+/// The evidence of a keeper slashed for reporting a wrong auction result
+pub struct Slashing {
+    /// The auction round reported
+    pub auction_round: u64,
+    /// The keeper slashed
+    pub keeper: Addr,
+    /// The other keepers slashed for reporting the same result
+    #[serde(default)]
+    pub reporters: Vec<Addr>,
+    /// The amount of the bonds slashed
+    pub amount: Uint128,
+    /// The winner of the auction reported by the keeper
+    pub reported_winner: String,
+    /// The winning bid reported by the keeper
+    pub reported_winning_bid: Uint128,
+    /// The winner of the auction according to the auction module
+    pub auction_winner: String,
+    /// The winning bid according to the auction module
+    pub auction_winning_bid: Uint128,
+    /// The time the keeper was slashed
+    pub slashed_at: u64,
+}
+
+#[cw_serde]
+pub struct SlashingsResponse {
+    pub slashings: Vec<Slashing>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+/// The operations paused by the pausers
+pub struct PauseStatus {
+    /// Whether users cannot join the pool
+    pub join: bool,
+    /// Whether users cannot exit the pool
+    pub exit: bool,
+    /// Whether the contract cannot bid on the auction. Users are not locked out of the pool before
+    /// the auction closes while bidding is paused
+    pub bid: bool,
+    /// Whether the auction cannot be settled
+    pub settle: bool,
+}
+
+#[cw_serde]
+pub struct NavResponse {
+    /// The net asset value of the pool in native denom
+    pub nav: Uint128,
+    /// The supply of the current LP denom
+    pub lp_supply: Uint128,
+    /// The value in native denom of one LP token
+    pub share_price: Decimal,
+}
+
+#[cw_serde]
+pub struct WithdrawableNowResponse {
+    /// The amount of native denom the address can withdraw from the pool now
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+/// A deposit that joins the pool on a later auction round
+pub struct PendingDeposit {
+    /// The auction round the deposit joins the pool on
+    pub auction_round: u64,
+    /// The amount of native denom deposited
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct PendingDepositsResponse {
+    pub deposits: Vec<PendingDeposit>,
+}
+
+#[cw_serde]
+pub struct PendingDepositTotalsResponse {
+    pub totals: Vec<(u64, Uint128)>,
+}
+
+#[cw_serde]
+/// An exit requested while the pool was locked, processed when the auction is settled
+pub struct PendingExit {
+    /// The auction round in which the exit was requested
+    pub auction_round: u64,
+    /// The LP subdenom of the escrowed LP tokens
+    pub lp_subdenom: u64,
+    /// The amount of LP tokens escrowed
+    pub amount: Uint128,
+    /// The part of the LP tokens escrowed charged as exit fee, at the rate of the time the exit was
+    /// requested. Its value stays in the bidding balance if the auction is lost
+    #[serde(default)]
+    pub fee_shares: Uint128,
+}
+
+#[cw_serde]
+pub struct PendingExitsResponse {
+    pub exits: Vec<(Addr, PendingExit)>,
+}
+
+#[cw_serde]
+/// A deposit kept in the pool for several auction rounds, its LP tokens escrowed by the contract
+pub struct Commitment {
+    /// The LP subdenom of the escrowed LP tokens
+    pub lp_subdenom: u64,
+    /// The amount of LP tokens escrowed
+    pub amount: Uint128,
+    /// The last auction round the deposit is committed to. The LP tokens are released once it is
+    /// settled
+    pub last_auction_round: u64,
+}
+
+#[cw_serde]
+pub struct CommitmentsResponse {
+    pub commitments: Vec<(Addr, Commitment)>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+/// Lifetime statistics of the pool
+pub struct PoolStats {
+    /// Number of auction rounds the contract bid on
+    pub rounds_participated: u64,
+    /// Number of auction rounds the contract won
+    pub rounds_won: u64,
+    /// Total amount of native denom bid, counting the highest bid of the contract on each settled
+    /// round
+    pub total_bid: Uint128,
+    /// Total amount of native denom burned by winning auctions
+    pub total_burned: Uint128,
+    /// Total amount of coins sent to the rewards fee address
+    pub total_fees: Vec<Coin>,
+    /// Total amount of basket coins sent to the treasure chest contracts
+    pub total_distributed: Vec<Coin>,
+    /// Number of addresses with funds in the pool for the unsettled auction
+    pub depositor_count: u64,
+}
+
+#[cw_serde]
+pub struct StatsResponse {
+    pub stats: PoolStats,
+}
+
 #[cw_serde]
 /// Config of the contract
 pub struct Config {
@@ -134,6 +872,214 @@ pub struct Config {
     /// The minimum return allowed in percentage. 5% means the contract cannot bid for more than
     /// 95% of the basket value
     pub min_return: Decimal,
+    /// Settings to value the auction basket on-chain
+    #[serde(default)]
+    pub pricing: PricingConfig,
+    /// How much the contract bids on the auction
+    #[serde(default)]
+    pub bid_strategy: BidStrategy,
+    /// Seconds before the auction closing time in which the contract can bid. Zero allows bids
+    /// during the whole auction round
+    #[serde(default)]
+    pub bid_window: u64,
+    /// Seconds before the auction closing time in which users cannot exit the pool. Zero locks
+    /// the pool only once the contract has bid
+    #[serde(default)]
+    pub lock_period_seconds: u64,
+    /// Share of the outstanding bid kept on top of it for re-bids, when users withdraw the unbid
+    /// surplus while the pool is locked
+    #[serde(default)]
+    pub rebid_buffer: Decimal,
+    /// Fee charged on exits, kept in the bidding balance for the remaining depositors
+    #[serde(default)]
+    pub exit_fee: ExitFeeSchedule,
+    /// What to do with the unused bidding balance when the auction is won
+    #[serde(default)]
+    pub surplus_policy: SurplusPolicy,
+    /// Seconds a proposed change of the rewards fee, the rewards fee address, the minimum return,
+    /// the pricing, the bid strategy, the exit fee, the surplus policy, the treasure chest code id
+    /// or the timelock itself waits before it can be applied
+    #[serde(default)]
+    pub config_timelock: u64,
+    /// Minimum amount of native denom a keeper must bond to settle the auction. Zero does not
+    /// require a bond
+    #[serde(default)]
+    pub keeper_min_bond: Uint128,
+    /// Seconds a keeper waits to claim the amount unbonded
+    #[serde(default)]
+    pub keeper_unbonding_period: u64,
+    /// Seconds a settlement reported by a keeper can be disputed before it can be finalized.
+    /// Zero settles the auction right away
+    #[serde(default)]
+    pub settlement_challenge_period: u64,
+    /// Number of distinct keepers that must report the same auction result to settle the auction.
+    /// Zero is treated as one
+    #[serde(default)]
+    pub settlement_quorum: u32,
+    /// Minimum amount of the deposits kept pending or committed, in native denom, and of the exit
+    /// requests, in LP tokens
+    #[serde(default)]
+    pub min_deposit: Uint128,
+}
+
+#[cw_serde]
+/// A change of the config waiting for its activation time
+pub struct ConfigProposal {
+    /// The new rewards fee
+    pub rewards_fee: Option<Decimal>,
+    /// The new address to receive the rewards fee
+    pub rewards_fee_addr: Option<Addr>,
+    /// The new minimum return
+    pub min_return: Option<Decimal>,
+    /// The new settings to value the auction basket on-chain
+    pub pricing: Option<PricingConfig>,
+    /// The new bid strategy
+    pub bid_strategy: Option<BidStrategy>,
+    /// The new exit fee schedule
+    pub exit_fee: Option<ExitFeeSchedule>,
+    /// The new surplus policy
+    pub surplus_policy: Option<SurplusPolicy>,
+    /// The new treasure chest code id
+    pub treasury_chest_code_id: Option<u64>,
+    /// The new config timelock
+    pub config_timelock: Option<u64>,
+    /// The time the change was proposed
+    pub proposed_at: u64,
+    /// The time from which the change can be applied
+    pub activates_at: u64,
+}
+
+#[cw_serde]
+#[derive(Default)]
+/// Fee charged on exits, growing linearly from the base fee to the max fee as the auction closing
+/// time approaches
+pub struct ExitFeeSchedule {
+    /// Fee charged on exits made before the fee starts growing. Value is between 0 and 1
+    pub base_fee: Decimal,
+    /// Fee charged on exits made at the auction closing time. Value is between the base fee and 1
+    pub max_fee: Decimal,
+    /// Seconds before the auction closing time from which the fee starts growing. Zero charges the
+    /// base fee until the auction closes
+    pub ramp_seconds: u64,
+}
+
+#[cw_serde]
+#[derive(Default)]
+/// What to do with the unused bidding balance when the auction is won
+pub enum SurplusPolicy {
+    /// Sends the unused bidding balance to the treasure chest, redeemable as native denom
+    #[default]
+    ToChest,
+    /// Keeps the unused bidding balance for the next auction round, sending the matching LP tokens
+    /// of the new round to the treasure chest
+    RollOver,
+}
+
+impl std::fmt::Display for SurplusPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SurplusPolicy::ToChest => write!(f, "to_chest"),
+            SurplusPolicy::RollOver => write!(f, "roll_over"),
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+/// Strategy used to pick the bid amount, between the minimum bid accepted by the auction module
+/// and the maximum bid allowed by the minimum return and the bidding balance
+pub enum BidStrategy {
+    /// Bids the minimum amount accepted by the auction module
+    #[default]
+    MinimalIncrement,
+    /// Bids a fraction of the maximum allowed bid. Value is between 0 and 1
+    JumpBid {
+        fraction: Decimal,
+    },
+    /// Bids the lowest multiple of the step accepted by the auction module
+    Ladder {
+        step: Uint128,
+    },
+    /// Bids the maximum allowed bid
+    MaxAllowed,
+}
+
+impl std::fmt::Display for BidStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BidStrategy::MinimalIncrement => write!(f, "minimal_increment"),
+            BidStrategy::JumpBid {
+                fraction,
+            } => write!(f, "jump_bid:{fraction}"),
+            BidStrategy::Ladder {
+                step,
+            } => write!(f, "ladder:{step}"),
+            BidStrategy::MaxAllowed => write!(f, "max_allowed"),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum PriceSource {
+    /// Price from the Injective oracle module. The base and quote decimals are used to scale the
+    /// price to chain units, and the quote asset must be the native denom
+    Oracle {
+        oracle_type: i32,
+        base: String,
+        quote: String,
+        base_decimals: u32,
+        quote_decimals: u32,
+    },
+    /// Mid price of an Injective exchange spot market, with the denom as base asset and the native
+    /// denom as quote asset. The mid price has no staleness guarantee, so it is only used to
+    /// cross-check a basket value supplied by a bidder and never for permissionless bids
+    SpotMarket {
+        market_id: String,
+    },
+    /// USD price from a price feed contract such as Pyth, converted to native denom with the
+    /// native denom feed. The decimals are the ones of the denom
+    PriceFeed {
+        feed_id: String,
+        decimals: u32,
+    },
+}
+
+#[cw_serde]
+/// Settings of the price feed contract used by the `PriceFeed` price sources
+pub struct PriceFeedConfig {
+    /// Address of the price feed contract
+    pub contract: Addr,
+    /// Feed id of the native denom USD price
+    pub native_feed_id: String,
+    /// Decimals of the native denom
+    pub native_decimals: u32,
+    /// Maximum confidence interval allowed, relative to the price. Value is between 0 and 1
+    pub max_confidence: Decimal,
+}
+
+#[cw_serde]
+pub struct DenomPriceSource {
+    /// The basket denom to be priced
+    pub denom: String,
+    /// Where to get the price of the denom from
+    pub source: PriceSource,
+}
+
+#[cw_serde]
+#[derive(Default)]
+/// Settings to value the auction basket on-chain
+pub struct PricingConfig {
+    /// The price source of each basket denom. Denoms without a price source are valued at zero
+    pub price_sources: Vec<DenomPriceSource>,
+    /// Maximum age in seconds of an oracle or price feed price. Must not be zero when an oracle or
+    /// price feed source is configured
+    pub max_price_age: u64,
+    /// Maximum relative difference allowed between the basket value sent to TryBid and the
+    /// on-chain valuation. Value is between 0 and 1
+    pub basket_value_tolerance: Decimal,
+    /// Price feed contract settings, required by the `PriceFeed` price sources
+    #[serde(default)]
+    pub price_feed: Option<PriceFeedConfig>,
 }
 
 #[cw_serde]
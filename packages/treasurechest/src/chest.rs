@@ -25,6 +25,8 @@ pub enum ExecuteMsg {
     Withdraw {},
     /// If balance is below >1< tickets worth (ADMIN only)
     ReturnDust { limit: Option<u32>},
+    /// add the funds sent to the rewards of the outstanding tickets (ADMIN only)
+    AddRewards {},
     /// change token factory type (ADMIN only)
     ChangeTokenFactory {
         token_factory_type: String,
@@ -29,6 +29,9 @@ pub enum ContractError {
     #[error("Too many outstanding redemption tokens {0} - min = {1}")]
     TicketsOutstanding(u128,u128),
 
+    #[error("no outstanding {0} tokens to add rewards to")]
+    NoTicketsOutstanding(String),
+
     #[error("Unauthorized")]
     Unauthorized {},
 